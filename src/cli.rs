@@ -173,11 +173,15 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
             whole_stream_command(Debug),
             whole_stream_command(Lines),
             whole_stream_command(Shells),
+            whole_stream_command(SplitBy),
             whole_stream_command(SplitColumn),
             whole_stream_command(SplitRow),
+            whole_stream_command(Tokenize),
             whole_stream_command(Lines),
             whole_stream_command(Reject),
             whole_stream_command(Reverse),
+            whole_stream_command(Rotate),
+            whole_stream_command(Collect),
             whole_stream_command(Trim),
             whole_stream_command(ToArray),
             whole_stream_command(ToBSON),
@@ -189,43 +193,69 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
             whole_stream_command(ToTSV),
             whole_stream_command(ToYAML),
             whole_stream_command(SortBy),
+            whole_stream_command(GroupBy),
+            whole_stream_command(Duplicates),
+            whole_stream_command(Uniq),
             whole_stream_command(Tags),
             whole_stream_command(First),
             whole_stream_command(Last),
             whole_stream_command(FromArray),
             whole_stream_command(FromArray),
             whole_stream_command(FromCSV),
+            whole_stream_command(FromFixedWidth),
             whole_stream_command(FromTSV),
             whole_stream_command(FromINI),
             whole_stream_command(FromBSON),
             whole_stream_command(FromJSON),
+            whole_stream_command(FromLogfmt),
+            whole_stream_command(FromCLF),
             whole_stream_command(FromDB),
             whole_stream_command(FromSQLite),
             whole_stream_command(FromTOML),
+            whole_stream_command(FromICS),
+            whole_stream_command(FromVCF),
             whole_stream_command(FromXML),
             whole_stream_command(FromYAML),
             whole_stream_command(FromYML),
             whole_stream_command(Pick),
+            whole_stream_command(Select),
+            whole_stream_command(Seq),
             whole_stream_command(Get),
             per_item_command(Remove),
             per_item_command(Open),
-            per_item_command(Post),
-            per_item_command(Where),
+            whole_stream_command(Post),
+            whole_stream_command(Fetch),
+            whole_stream_command(Where),
             whole_stream_command(Config),
             whole_stream_command(SkipWhile),
+            whole_stream_command(TakeWhile),
+            whole_stream_command(ParEach),
             per_item_command(Enter),
             per_item_command(Help),
             whole_stream_command(Exit),
+            whole_stream_command(Inspect),
             whole_stream_command(Autoview),
+            whole_stream_command(BuildString),
+            whole_stream_command(Calc),
+            whole_stream_command(Round),
+            whole_stream_command(Random),
+            whole_stream_command(Median),
+            whole_stream_command(Mode),
             per_item_command(Cpy),
             whole_stream_command(Date),
             per_item_command(Mkdir),
             per_item_command(Move),
+            per_item_command(Touch),
             whole_stream_command(Save),
             whole_stream_command(Table),
             whole_stream_command(VTable),
+            whole_stream_command(Values),
             whole_stream_command(Version),
             whole_stream_command(Which),
+            whole_stream_command(Window),
+            whole_stream_command(Zip),
+            whole_stream_command(Join),
+            whole_stream_command(Source),
         ]);
 
         #[cfg(feature = "clipboard")]
@@ -302,22 +332,9 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
                 }
             }
 
-            LineResult::Error(mut line, err) => {
+            LineResult::Error(line, err) => {
                 rl.add_history_entry(line.clone());
-                let diag = err.to_diagnostic();
-                context.with_host(|host| {
-                    let writer = host.err_termcolor();
-                    line.push_str(" ");
-                    let files = crate::parser::Files::new(line);
-                    let _ = std::panic::catch_unwind(move || {
-                        let _ = language_reporting::emit(
-                            &mut writer.lock(),
-                            &files,
-                            &diag,
-                            &language_reporting::DefaultConfig,
-                        );
-                    });
-                })
+                context.with_host(|host| print_err(err, host, line));
             }
 
             LineResult::Break => {
@@ -339,7 +356,22 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-enum LineResult {
+pub(crate) fn print_err(err: ShellError, host: &dyn Host, mut line: String) {
+    let diag = err.to_diagnostic();
+    let writer = host.err_termcolor();
+    line.push_str(" ");
+    let files = crate::parser::Files::new(line);
+    let _ = std::panic::catch_unwind(move || {
+        let _ = language_reporting::emit(
+            &mut writer.lock(),
+            &files,
+            &diag,
+            &language_reporting::DefaultConfig,
+        );
+    });
+}
+
+pub(crate) enum LineResult {
     Success(String),
     Error(String, ShellError),
     CtrlC,
@@ -349,7 +381,10 @@ enum LineResult {
     FatalError(String, ShellError),
 }
 
-async fn process_line(readline: Result<String, ReadlineError>, ctx: &mut Context) -> LineResult {
+pub(crate) async fn process_line(
+    readline: Result<String, ReadlineError>,
+    ctx: &mut Context,
+) -> LineResult {
     match &readline {
         Ok(line) if line.trim() == "" => LineResult::Success(line.clone()),
 
@@ -386,6 +421,7 @@ async fn process_line(readline: Result<String, ReadlineError>, ctx: &mut Context
             }
 
             let mut input = ClassifiedInputStream::new();
+            let mut pending_exits = vec![];
 
             let mut iter = pipeline.commands.into_iter().peekable();
 
@@ -393,6 +429,10 @@ async fn process_line(readline: Result<String, ReadlineError>, ctx: &mut Context
                 let item: Option<ClassifiedCommand> = iter.next();
                 let next: Option<&ClassifiedCommand> = iter.peek();
 
+                if let Some(pending_exit) = input.pending_exit.take() {
+                    pending_exits.push(pending_exit);
+                }
+
                 input = match (item, next) {
                     (None, _) => break,
 
@@ -456,6 +496,12 @@ async fn process_line(readline: Result<String, ReadlineError>, ctx: &mut Context
                 }
             }
 
+            for pending_exit in pending_exits {
+                if let Err(err) = pending_exit.check() {
+                    return LineResult::Error(line.clone(), err);
+                }
+            }
+
             LineResult::Success(line.clone())
         }
         Err(ReadlineError::Interrupted) => LineResult::CtrlC,
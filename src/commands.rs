@@ -3,54 +3,81 @@ pub(crate) mod macros;
 
 pub(crate) mod args;
 pub(crate) mod autoview;
+pub(crate) mod build_string;
+pub(crate) mod calc;
 pub(crate) mod cd;
 pub(crate) mod classified;
 pub(crate) mod clip;
+pub(crate) mod collect;
 pub(crate) mod command;
 pub(crate) mod config;
 pub(crate) mod cp;
 pub(crate) mod date;
 pub(crate) mod debug;
+pub(crate) mod duplicates;
 pub(crate) mod enter;
 pub(crate) mod exit;
+pub(crate) mod fetch;
 pub(crate) mod first;
 pub(crate) mod from_array;
 pub(crate) mod from_bson;
+pub(crate) mod from_clf;
 pub(crate) mod from_csv;
+pub(crate) mod from_fixed_width;
+pub(crate) mod from_ics;
 pub(crate) mod from_ini;
 pub(crate) mod from_json;
+pub(crate) mod from_logfmt;
 pub(crate) mod from_sqlite;
 pub(crate) mod from_toml;
 pub(crate) mod from_tsv;
+pub(crate) mod from_vcf;
 pub(crate) mod from_xml;
 pub(crate) mod from_yaml;
 pub(crate) mod get;
+pub(crate) mod group_by;
 pub(crate) mod help;
+pub(crate) mod inspect;
+pub(crate) mod join;
 pub(crate) mod last;
 pub(crate) mod lines;
 pub(crate) mod ls;
+pub(crate) mod median;
 pub(crate) mod mkdir;
+pub(crate) mod mode;
 pub(crate) mod mv;
 pub(crate) mod next;
 pub(crate) mod nth;
 pub(crate) mod open;
+pub(crate) mod par_each;
 pub(crate) mod pick;
 pub(crate) mod plugin;
 pub(crate) mod post;
+pub(crate) mod predicate;
 pub(crate) mod prev;
 pub(crate) mod ps;
+pub(crate) mod random;
 pub(crate) mod reject;
 pub(crate) mod reverse;
 pub(crate) mod rm;
+pub(crate) mod rotate;
+pub(crate) mod round;
 pub(crate) mod save;
+pub(crate) mod select;
+pub(crate) mod seq;
 pub(crate) mod shells;
 pub(crate) mod size;
 pub(crate) mod skip_while;
 pub(crate) mod sort_by;
+pub(crate) mod source;
+pub(crate) mod split_by;
 pub(crate) mod split_column;
 pub(crate) mod split_row;
 pub(crate) mod table;
 pub(crate) mod tags;
+pub(crate) mod take_while;
+#[cfg(test)]
+pub(crate) mod testing;
 pub(crate) mod to_array;
 pub(crate) mod to_bson;
 pub(crate) mod to_csv;
@@ -59,14 +86,23 @@ pub(crate) mod to_sqlite;
 pub(crate) mod to_toml;
 pub(crate) mod to_tsv;
 pub(crate) mod to_yaml;
+pub(crate) mod tokenize;
+pub(crate) mod touch;
 pub(crate) mod trim;
+pub(crate) mod uniq;
+pub(crate) mod values;
 pub(crate) mod version;
 pub(crate) mod vtable;
 pub(crate) mod where_;
 pub(crate) mod which_;
+pub(crate) mod window;
+pub(crate) mod zip;
 
 pub(crate) use autoview::Autoview;
+pub(crate) use build_string::BuildString;
+pub(crate) use calc::Calc;
 pub(crate) use cd::CD;
+pub(crate) use collect::Collect;
 pub(crate) use command::{
     per_item_command, whole_stream_command, Command, PerItemCommand, RawCommandArgs,
     UnevaluatedCallInfo, WholeStreamCommand,
@@ -76,47 +112,68 @@ pub(crate) use config::Config;
 pub(crate) use cp::Cpy;
 pub(crate) use date::Date;
 pub(crate) use debug::Debug;
+pub(crate) use duplicates::Duplicates;
 pub(crate) use enter::Enter;
 pub(crate) use exit::Exit;
+pub(crate) use fetch::Fetch;
 pub(crate) use first::First;
 pub(crate) use from_array::FromArray;
 pub(crate) use from_bson::FromBSON;
+pub(crate) use from_clf::FromCLF;
 pub(crate) use from_csv::FromCSV;
+pub(crate) use from_fixed_width::FromFixedWidth;
+pub(crate) use from_ics::FromICS;
 pub(crate) use from_ini::FromINI;
 pub(crate) use from_json::FromJSON;
+pub(crate) use from_logfmt::FromLogfmt;
 pub(crate) use from_sqlite::FromDB;
 pub(crate) use from_sqlite::FromSQLite;
 pub(crate) use from_toml::FromTOML;
 pub(crate) use from_tsv::FromTSV;
+pub(crate) use from_vcf::FromVCF;
 pub(crate) use from_xml::FromXML;
 pub(crate) use from_yaml::FromYAML;
 pub(crate) use from_yaml::FromYML;
 pub(crate) use get::Get;
+pub(crate) use group_by::GroupBy;
 pub(crate) use help::Help;
+pub(crate) use inspect::Inspect;
+pub(crate) use join::Join;
 pub(crate) use last::Last;
 pub(crate) use lines::Lines;
 pub(crate) use ls::LS;
+pub(crate) use median::Median;
 pub(crate) use mkdir::Mkdir;
+pub(crate) use mode::Mode;
 pub(crate) use mv::Move;
 pub(crate) use next::Next;
 pub(crate) use nth::Nth;
 pub(crate) use open::Open;
+pub(crate) use par_each::ParEach;
 pub(crate) use pick::Pick;
 pub(crate) use post::Post;
 pub(crate) use prev::Previous;
 pub(crate) use ps::PS;
+pub(crate) use random::Random;
 pub(crate) use reject::Reject;
 pub(crate) use reverse::Reverse;
 pub(crate) use rm::Remove;
+pub(crate) use rotate::Rotate;
+pub(crate) use round::Round;
 pub(crate) use save::Save;
+pub(crate) use select::Select;
+pub(crate) use seq::Seq;
 pub(crate) use shells::Shells;
 pub(crate) use size::Size;
 pub(crate) use skip_while::SkipWhile;
 pub(crate) use sort_by::SortBy;
+pub(crate) use source::Source;
+pub(crate) use split_by::SplitBy;
 pub(crate) use split_column::SplitColumn;
 pub(crate) use split_row::SplitRow;
 pub(crate) use table::Table;
 pub(crate) use tags::Tags;
+pub(crate) use take_while::TakeWhile;
 pub(crate) use to_array::ToArray;
 pub(crate) use to_bson::ToBSON;
 pub(crate) use to_csv::ToCSV;
@@ -126,8 +183,14 @@ pub(crate) use to_sqlite::ToSQLite;
 pub(crate) use to_toml::ToTOML;
 pub(crate) use to_tsv::ToTSV;
 pub(crate) use to_yaml::ToYAML;
+pub(crate) use tokenize::Tokenize;
+pub(crate) use touch::Touch;
 pub(crate) use trim::Trim;
+pub(crate) use uniq::Uniq;
+pub(crate) use values::Values;
 pub(crate) use version::Version;
 pub(crate) use vtable::VTable;
 pub(crate) use where_::Where;
 pub(crate) use which_::Which;
+pub(crate) use window::Window;
+pub(crate) use zip::Zip;
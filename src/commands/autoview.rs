@@ -1,5 +1,6 @@
 use crate::commands::{RawCommandArgs, WholeStreamCommand};
 use crate::errors::ShellError;
+use crate::format::TableView;
 use crate::prelude::*;
 
 pub struct Autoview;
@@ -50,38 +51,16 @@ pub fn autoview(
                 let text = context.expect_command("textview");
                 let result = text.run(raw.with_input(input), &context.commands);
                 result.collect::<Vec<_>>().await;
-            } else if equal_shapes(&input) {
-                let table = context.expect_command("table");
-                let result = table.run(raw.with_input(input), &context.commands);
-                result.collect::<Vec<_>>().await;
             } else {
-                let table = context.expect_command("table");
-                let result = table.run(raw.with_input(input), &context.commands);
-                result.collect::<Vec<_>>().await;
+                let mut host = context.host.lock().unwrap();
+                if let Some(view) = TableView::from_list(&input) {
+                    handle_unexpected(&mut *host, |host| crate::format::print_view(&view, host));
+                }
             }
         }
     }))
 }
 
-fn equal_shapes(input: &Vec<Tagged<Value>>) -> bool {
-    let mut items = input.iter();
-
-    let item = match items.next() {
-        Some(item) => item,
-        None => return false,
-    };
-
-    let desc = item.data_descriptors();
-
-    for item in items {
-        if desc != item.data_descriptors() {
-            return false;
-        }
-    }
-
-    true
-}
-
 fn is_single_text_value(input: &Vec<Tagged<Value>>) -> bool {
     if input.len() != 1 {
         return false;
@@ -0,0 +1,69 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::Value;
+use crate::prelude::*;
+
+pub struct BuildString;
+
+#[derive(Deserialize)]
+pub struct BuildStringArgs {
+    rest: Vec<Tagged<Value>>,
+}
+
+impl WholeStreamCommand for BuildString {
+    fn name(&self) -> &str {
+        "build-string"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("build-string").rest(SyntaxType::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Concatenate the piped strings and the arguments into a single string."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, build_string)?.run()
+    }
+}
+
+fn string_part(value: &Tagged<Value>) -> Result<String, ShellError> {
+    match value.item() {
+        Value::Primitive(p) => match value.as_string() {
+            Ok(s) => Ok(s),
+            Err(_) => Ok(p.format(None)),
+        },
+        other => Err(ShellError::labeled_error(
+            "Expected a primitive value to build a string",
+            format!("{} can not be converted to a string", other.type_name()),
+            value.span(),
+        )),
+    }
+}
+
+fn build_string(
+    BuildStringArgs { rest }: BuildStringArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut output = String::new();
+
+        for value in values.iter().chain(rest.iter()) {
+            match string_part(value) {
+                Ok(part) => output.push_str(&part),
+                Err(err) => yield Err(err),
+            }
+        }
+
+        yield ReturnSuccess::value(Value::string(output).simple_spanned(name));
+    };
+
+    Ok(stream.to_output_stream())
+}
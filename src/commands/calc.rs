@@ -0,0 +1,262 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{Primitive, Value};
+use crate::prelude::*;
+use std::str::FromStr;
+
+pub struct Calc;
+
+#[derive(Deserialize)]
+pub struct CalcArgs {
+    column: Tagged<String>,
+    expression: Tagged<String>,
+}
+
+impl WholeStreamCommand for Calc {
+    fn name(&self) -> &str {
+        "calc"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("calc")
+            .required("column", SyntaxType::Member)
+            .required("expression", SyntaxType::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Add a column computed from an arithmetic expression over other columns."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, calc)?.run()
+    }
+}
+
+#[derive(Debug)]
+enum Expression {
+    Literal(Number),
+    Column(String),
+    Binary(Box<Expression>, char, Box<Expression>),
+}
+
+struct ExpressionParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ExpressionParser {
+    fn parse(source: &str) -> Result<Expression, String> {
+        let mut parser = ExpressionParser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+
+        let expression = parser.sum()?;
+
+        match parser.peek() {
+            None => Ok(expression),
+            Some(c) => Err(format!("unexpected '{}'", c)),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        while let Some(c) = self.chars.get(self.pos) {
+            if !c.is_whitespace() {
+                return Some(*c);
+            }
+            self.pos += 1;
+        }
+
+        None
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.pos < self.chars.len() && predicate(self.chars[self.pos]) {
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn sum(&mut self) -> Result<Expression, String> {
+        let mut left = self.product()?;
+
+        while let Some(op) = self.peek().filter(|c| *c == '+' || *c == '-') {
+            self.pos += 1;
+            let right = self.product()?;
+            left = Expression::Binary(Box::new(left), op, Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Expression, String> {
+        let mut left = self.atom()?;
+
+        while let Some(op) = self.peek().filter(|c| *c == '*' || *c == '/') {
+            self.pos += 1;
+            let right = self.atom()?;
+            left = Expression::Binary(Box::new(left), op, Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn atom(&mut self) -> Result<Expression, String> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.sum()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.pos += 1;
+                        Ok(inner)
+                    }
+                    _ => Err("expected ')'".to_string()),
+                }
+            }
+            Some('-') => {
+                self.pos += 1;
+                let negated = self.atom()?;
+                Ok(Expression::Binary(
+                    Box::new(Expression::Literal(Number::Int(0))),
+                    '-',
+                    Box::new(negated),
+                ))
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let literal = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                match (i64::from_str(&literal), Decimal::from_str(&literal)) {
+                    (Ok(int), _) => Ok(Expression::Literal(Number::Int(int))),
+                    (_, Ok(decimal)) => Ok(Expression::Literal(Number::Decimal(decimal))),
+                    _ => Err(format!("'{}' is not a number", literal)),
+                }
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => Ok(Expression::Column(
+                self.take_while(|c| c.is_alphanumeric() || c == '_'),
+            )),
+            Some(c) => Err(format!("unexpected '{}'", c)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+fn as_decimal(n: &Number) -> Decimal {
+    match n {
+        Number::Int(i) => Decimal::from(*i),
+        Number::Decimal(d) => *d,
+    }
+}
+
+fn apply(left: Number, op: char, right: Number) -> Result<Number, String> {
+    let divides_by_zero = match &right {
+        Number::Int(i) => *i == 0,
+        Number::Decimal(d) => *d == Decimal::new(0, 0),
+    };
+
+    if op == '/' && divides_by_zero {
+        return Err("division by zero".to_string());
+    }
+
+    if let (Number::Int(l), Number::Int(r)) = (&left, &right) {
+        let int = match op {
+            '+' => l.checked_add(*r),
+            '-' => l.checked_sub(*r),
+            '*' => l.checked_mul(*r),
+            _ if l.checked_rem(*r) == Some(0) => l.checked_div(*r),
+            _ => None,
+        };
+
+        // Anything that doesn't stay a whole i64 falls through to decimals
+        if let Some(int) = int {
+            return Ok(Number::Int(int));
+        }
+    }
+
+    let (l, r) = (as_decimal(&left), as_decimal(&right));
+
+    let decimal = match op {
+        '+' => l.checked_add(r),
+        '-' => l.checked_sub(r),
+        '*' => l.checked_mul(r),
+        _ => l.checked_div(r),
+    };
+
+    match decimal {
+        Some(decimal) => Ok(Number::Decimal(decimal)),
+        None => Err("result is out of range".to_string()),
+    }
+}
+
+fn evaluate(expression: &Expression, row: &Tagged<Value>) -> Result<Number, String> {
+    match expression {
+        Expression::Literal(n) => Ok(*n),
+        Expression::Column(name) => match row.get_data_by_key(name) {
+            Some(Tagged {
+                item: Value::Primitive(Primitive::Int(i)),
+                ..
+            }) => Ok(Number::Int(*i)),
+            Some(Tagged {
+                item: Value::Primitive(Primitive::Decimal(d)),
+                ..
+            }) => Ok(Number::Decimal(*d)),
+            Some(other) => Err(format!(
+                "column '{}' is {}, not a number",
+                name,
+                other.type_name()
+            )),
+            None => Err(format!("column '{}' is missing", name)),
+        },
+        Expression::Binary(left, op, right) => {
+            apply(evaluate(left, row)?, *op, evaluate(right, row)?)
+        }
+    }
+}
+
+fn calc(
+    CalcArgs { column, expression }: CalcArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let parsed = match ExpressionParser::parse(&expression.item) {
+        Ok(parsed) => parsed,
+        Err(reason) => {
+            return Err(ShellError::labeled_error(
+                "Could not parse calc expression",
+                reason,
+                expression.span(),
+            ))
+        }
+    };
+
+    Ok(input
+        .values
+        .map(move |row| match evaluate(&parsed, &row) {
+            Ok(result) => match row.insert_data_at_path(
+                row.tag(),
+                &column.item,
+                Value::Primitive(Primitive::number(result)),
+            ) {
+                Some(row) => ReturnSuccess::value(row),
+                None => Err(ShellError::labeled_error_with_secondary(
+                    "Could not add calculated column",
+                    "requires a table",
+                    column.span(),
+                    "value originates from here",
+                    row.span(),
+                )),
+            },
+            Err(reason) => Err(ShellError::labeled_error_with_secondary(
+                "Could not calculate column",
+                reason,
+                expression.span(),
+                "row originates from here",
+                row.span(),
+            )),
+        })
+        .to_output_stream())
+}
@@ -6,8 +6,8 @@ use futures::stream::StreamExt;
 use futures_codec::{Decoder, Encoder, Framed};
 use log::{log_enabled, trace};
 use std::io::{Error, ErrorKind};
-use std::sync::Arc;
-use subprocess::Exec;
+use std::sync::{Arc, Mutex};
+use subprocess::{Exec, ExitStatus, Popen};
 
 /// A simple `Codec` implementation that splits up data into lines.
 pub struct LinesCodec {}
@@ -48,6 +48,60 @@ impl Decoder for LinesCodec {
 pub(crate) struct ClassifiedInputStream {
     pub(crate) objects: InputStream,
     pub(crate) stdin: Option<std::fs::File>,
+    pub(crate) pending_exit: Option<PendingExit>,
+}
+
+/// An external command whose output feeds the next command. Its exit status is checked
+/// once the pipeline has finished, as waiting any earlier could block on a full pipe.
+pub(crate) enum PendingExit {
+    Running(Popen, Span),
+    Drained(Arc<Mutex<Option<ShellError>>>),
+}
+
+impl PendingExit {
+    pub(crate) fn check(self) -> Result<(), ShellError> {
+        match self {
+            PendingExit::Running(mut popen, name_span) => {
+                match exit_status_error(popen.wait()?, name_span, true) {
+                    Some(err) => Err(err),
+                    None => Ok(()),
+                }
+            }
+            PendingExit::Drained(failure) => match failure.lock().unwrap().take() {
+                Some(err) => Err(err),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+// A command feeding another is sent SIGPIPE when the reader stops early, as `first` does,
+// which isn't a failure of that command. The shell running it reports that as 128 + 13.
+fn exit_status_error(status: ExitStatus, name_span: Span, piped: bool) -> Option<ShellError> {
+    match status {
+        ExitStatus::Exited(0) => None,
+        ExitStatus::Exited(141) | ExitStatus::Signaled(13) if piped => None,
+        ExitStatus::Exited(code) => Some(ShellError::labeled_error(
+            format!("External command exited with code {}", code),
+            "command failed",
+            name_span,
+        )),
+        ExitStatus::Signaled(signal) => Some(ShellError::labeled_error(
+            format!("External command was terminated by signal {}", signal),
+            "command failed",
+            name_span,
+        )),
+        ExitStatus::Other(status) => Some(ShellError::labeled_error(
+            format!("External command stopped with status {}", status),
+            "command failed",
+            name_span,
+        )),
+        ExitStatus::Undetermined => Some(ShellError::labeled_error(
+            "External command ended with an unknown status",
+            "command failed",
+            name_span,
+        )),
+    }
 }
 
 impl ClassifiedInputStream {
@@ -55,6 +109,7 @@ impl ClassifiedInputStream {
         ClassifiedInputStream {
             objects: VecDeque::new().into(),
             stdin: None,
+            pending_exit: None,
         }
     }
 
@@ -62,6 +117,7 @@ impl ClassifiedInputStream {
         ClassifiedInputStream {
             objects: stream.into(),
             stdin: None,
+            pending_exit: None,
         }
     }
 
@@ -69,6 +125,7 @@ impl ClassifiedInputStream {
         ClassifiedInputStream {
             objects: VecDeque::new().into(),
             stdin: Some(stdout),
+            pending_exit: None,
         }
     }
 }
@@ -352,24 +409,42 @@ impl ExternalCommand {
         let mut popen = process.popen()?;
 
         match stream_next {
-            StreamNext::Last => {
-                popen.wait()?;
-                Ok(ClassifiedInputStream::new())
-            }
+            StreamNext::Last => match exit_status_error(popen.wait()?, name_span, false) {
+                Some(err) => Err(err),
+                None => Ok(ClassifiedInputStream::new()),
+            },
             StreamNext::External => {
                 let stdout = popen.stdout.take().unwrap();
-                Ok(ClassifiedInputStream::from_stdout(stdout))
+                Ok(ClassifiedInputStream {
+                    pending_exit: Some(PendingExit::Running(popen, name_span)),
+                    ..ClassifiedInputStream::from_stdout(stdout)
+                })
             }
             StreamNext::Internal => {
                 let stdout = popen.stdout.take().unwrap();
                 let file = futures::io::AllowStdIo::new(stdout);
-                let stream = Framed::new(file, LinesCodec {});
-                let stream = stream.map(move |line| {
-                    Tagged::from_simple_spanned_item(Value::string(line.unwrap()), name_span)
-                });
-                Ok(ClassifiedInputStream::from_input_stream(
-                    stream.boxed() as BoxStream<'static, Tagged<Value>>
-                ))
+                let mut lines = Framed::new(file, LinesCodec {});
+                let failure = Arc::new(Mutex::new(None));
+                let drained = failure.clone();
+                let stream = async_stream_block! {
+                    while let Some(line) = lines.next().await {
+                        yield Tagged::from_simple_spanned_item(Value::string(line.unwrap()), name_span);
+                    }
+
+                    // The whole output was read, so the command has finished with it
+                    let status = popen.wait();
+                    let error = match status {
+                        Ok(status) => exit_status_error(status, name_span, false),
+                        Err(err) => Some(ShellError::from(err)),
+                    };
+                    *drained.lock().unwrap() = error;
+                };
+                Ok(ClassifiedInputStream {
+                    pending_exit: Some(PendingExit::Drained(failure)),
+                    ..ClassifiedInputStream::from_input_stream(
+                        stream.boxed() as BoxStream<'static, Tagged<Value>>
+                    )
+                })
             }
         }
     }
@@ -0,0 +1,42 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::Value;
+use crate::prelude::*;
+
+pub struct Collect;
+
+impl WholeStreamCommand for Collect {
+    fn name(&self) -> &str {
+        "collect"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("collect")
+    }
+
+    fn usage(&self) -> &str {
+        "Drain the input and output it as a single list."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        collect(args, registry)
+    }
+}
+
+fn collect(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_span = args.name_span();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        yield ReturnSuccess::value(Value::List(values).simple_spanned(name_span));
+    };
+
+    Ok(stream.to_output_stream())
+}
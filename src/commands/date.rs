@@ -1,8 +1,9 @@
 use crate::errors::ShellError;
-use crate::object::{Dictionary, Value};
+use crate::object::{Dictionary, Primitive, Value};
 use crate::prelude::*;
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Duration, Local, Utc};
 
+use crate::commands::command::EvaluatedWholeStreamCommandArgs;
 use crate::commands::WholeStreamCommand;
 use crate::parser::registry::Signature;
 use chrono::{Datelike, TimeZone, Timelike};
@@ -18,12 +19,14 @@ impl WholeStreamCommand for Date {
 
     fn signature(&self) -> Signature {
         Signature::build("date")
+            .optional("operation", SyntaxType::Any)
+            .optional("duration", SyntaxType::Any)
             .switch("utc")
             .switch("local")
     }
 
     fn usage(&self) -> &str {
-        "Get the current datetime."
+        "Get the current datetime, or shift dates with `date add 7day` / `date sub 2wk`."
     }
 
     fn run(
@@ -75,9 +78,172 @@ where
     Tagged::from_simple_spanned_item(Value::Object(Dictionary::from(indexmap)), span)
 }
 
+// Durations are written as a whole number followed by a unit, eg) 30day or 2wks
+fn parse_duration(s: &str, span: Span) -> Result<Duration, ShellError> {
+    let parse_failure = || {
+        ShellError::labeled_error(
+            "Could not parse duration",
+            "expected a duration like 30day (sec, min, hr, day or wk)",
+            span,
+        )
+    };
+
+    let unit_start = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(unit_start) if unit_start > 0 => unit_start,
+        _ => return Err(parse_failure()),
+    };
+    let (amount, unit) = s.split_at(unit_start);
+
+    let seconds_per_unit = match unit.trim_end_matches('s') {
+        "sec" => 1,
+        "min" => 60,
+        "hr" => 60 * 60,
+        "day" => 60 * 60 * 24,
+        "wk" => 60 * 60 * 24 * 7,
+        _ => return Err(parse_failure()),
+    };
+
+    // chrono panics on durations past i64::MAX milliseconds, so stop well before that
+    let seconds = amount
+        .parse::<i64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(seconds_per_unit))
+        .filter(|seconds| *seconds <= i64::max_value() / 1000);
+
+    match seconds {
+        Some(seconds) => Ok(Duration::seconds(seconds)),
+        None => Err(ShellError::labeled_error(
+            "Date is out of range",
+            "duration is too large",
+            span,
+        )),
+    }
+}
+
+// Reads back a datetime from the row `date` describes it with
+fn record_to_date(value: &Value) -> Option<DateTime<Utc>> {
+    let part = |name: &str| value.get_data_by_key(name)?.as_i64().ok();
+
+    let timezone = value.get_data_by_key("timezone")?.as_string().ok()?;
+    let offset = match timezone.as_str() {
+        "UTC" => "Z",
+        offset => offset,
+    };
+
+    let rfc3339 = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        part("year")?,
+        part("month")?,
+        part("day")?,
+        part("hour")?,
+        part("minute")?,
+        part("second")?,
+        offset
+    );
+
+    DateTime::parse_from_rfc3339(&rfc3339)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn date_math(
+    args: EvaluatedWholeStreamCommandArgs,
+    operation: Tagged<Value>,
+) -> Result<OutputStream, ShellError> {
+    let span = args.call_info.name_span;
+
+    let negate = match operation.as_string()?.as_str() {
+        "add" => false,
+        "sub" => true,
+        _ => {
+            return Err(ShellError::labeled_error(
+                "Unknown date operation",
+                "expected add or sub",
+                operation.span(),
+            ))
+        }
+    };
+
+    let duration = match args.nth(1) {
+        Some(duration) => {
+            let d = parse_duration(&duration.as_string()?, duration.span())?;
+            if negate {
+                -d
+            } else {
+                d
+            }
+        }
+        None => {
+            return Err(ShellError::labeled_error(
+                "date math requires a duration",
+                "needs parameter",
+                span,
+            ))
+        }
+    };
+
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        // Without any dates coming in, shift the current datetime
+        if values.is_empty() {
+            match Utc::now().checked_add_signed(duration) {
+                Some(dt) => yield ReturnSuccess::value(
+                    Value::Primitive(Primitive::Date(dt)).simple_spanned(span),
+                ),
+                None => yield Err(ShellError::labeled_error(
+                    "Date is out of range",
+                    "shifts past the supported dates",
+                    span,
+                )),
+            }
+        }
+
+        for value in values {
+            let value_tag = value.tag();
+            let date = match &value.item {
+                Value::Primitive(Primitive::Date(dt)) => Some(*dt),
+                Value::Object(_) => record_to_date(&value.item),
+                _ => None,
+            };
+
+            match date {
+                Some(dt) => match dt.checked_add_signed(duration) {
+                    Some(dt) => yield ReturnSuccess::value(
+                        Value::Primitive(Primitive::Date(dt)).tagged(value_tag),
+                    ),
+                    None => yield Err(ShellError::labeled_error_with_secondary(
+                        "Date is out of range",
+                        "shifts past the supported dates",
+                        span,
+                        "value originates from here",
+                        value_tag.span,
+                    )),
+                },
+                None => yield Err(ShellError::type_mismatch(
+                    "a date or a row from date",
+                    value.tagged_type_name(),
+                    span,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
 pub fn date(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
     let args = args.evaluate_once(registry)?;
 
+    // `date now` is the same as `date`, so it can start a pipeline of date math
+    match args.nth(0).cloned() {
+        Some(ref operation) if operation.as_string().ok() == Some("now".to_string()) => {}
+        Some(operation) => return date_math(args, operation),
+        None => {}
+    }
+
     let mut date_out = VecDeque::new();
     let span = args.call_info.name_span;
 
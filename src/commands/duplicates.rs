@@ -0,0 +1,182 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::TaggedDictBuilder;
+use crate::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+pub struct Duplicates;
+
+#[derive(Deserialize)]
+pub struct DuplicatesArgs {
+    count: bool,
+}
+
+impl WholeStreamCommand for Duplicates {
+    fn name(&self) -> &str {
+        "duplicates"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("duplicates").switch("count")
+    }
+
+    fn usage(&self) -> &str {
+        "Show the rows that appear more than once, with how often each appears when given '--count'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, duplicates)?.run()
+    }
+}
+
+// Rows are compared by what they hold, not by where they were read from
+fn same_value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            left.entries.len() == right.entries.len()
+                && left.entries.iter().zip(right.entries.iter()).all(
+                    |((left_key, left), (right_key, right))| {
+                        left_key == right_key && same_value(&left.item, &right.item)
+                    },
+                )
+        }
+        (Value::List(left), Value::List(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| same_value(&left.item, &right.item))
+        }
+        (left, right) => left == right,
+    }
+}
+
+// Hashes what a value holds the same way same_value compares it, so equal
+// values always land in the same bucket
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    std::mem::discriminant(value).hash(state);
+
+    match value {
+        Value::Primitive(primitive) => {
+            std::mem::discriminant(primitive).hash(state);
+
+            match primitive {
+                Primitive::Int(i) => i.hash(state),
+                Primitive::BigInt(i) => i.hash(state),
+                Primitive::Decimal(d) => d.normalize().hash(state),
+                Primitive::Bytes(b) => b.hash(state),
+                Primitive::String(s) => s.hash(state),
+                Primitive::Boolean(b) => b.hash(state),
+                Primitive::Date(d) => d.hash(state),
+                Primitive::Path(p) => p.hash(state),
+                _ => {}
+            }
+        }
+        Value::Object(o) => {
+            for (key, value) in o.entries.iter() {
+                key.hash(state);
+                hash_value(&value.item, state);
+            }
+        }
+        Value::List(l) => {
+            l.len().hash(state);
+            for value in l {
+                hash_value(&value.item, state);
+            }
+        }
+        Value::Binary(b) => b.hash(state),
+        Value::Block(_) => {}
+    }
+}
+
+/// Counts values by what they hold, in the order they first appeared. Values
+/// are bucketed by hash, so each one is only compared with the few that share it.
+#[derive(Default)]
+pub(crate) struct ValueCounts {
+    buckets: HashMap<u64, Vec<usize>>,
+    counts: Vec<(Tagged<Value>, usize)>,
+}
+
+impl ValueCounts {
+    fn find(&self, hash: u64, value: &Value) -> Option<usize> {
+        self.buckets.get(&hash).and_then(|bucket| {
+            bucket
+                .iter()
+                .find(|index| same_value(&self.counts[**index].0.item, value))
+                .cloned()
+        })
+    }
+
+    fn hash(value: &Value) -> u64 {
+        let mut state = DefaultHasher::new();
+        hash_value(value, &mut state);
+        state.finish()
+    }
+
+    pub(crate) fn add(&mut self, value: Tagged<Value>) {
+        let hash = ValueCounts::hash(&value.item);
+
+        match self.find(hash, &value.item) {
+            Some(index) => self.counts[index].1 += 1,
+            None => {
+                self.buckets
+                    .entry(hash)
+                    .or_insert_with(Vec::new)
+                    .push(self.counts.len());
+                self.counts.push((value, 1));
+            }
+        }
+    }
+
+    pub(crate) fn contains(&self, value: &Value) -> bool {
+        self.find(ValueCounts::hash(value), value).is_some()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.buckets.clear();
+        self.counts.clear();
+    }
+
+    pub(crate) fn into_counts(self) -> Vec<(Tagged<Value>, usize)> {
+        self.counts
+    }
+}
+
+pub fn duplicates(
+    DuplicatesArgs { count }: DuplicatesArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        // Each repeated row is shown once, at the place it first appeared
+        let mut seen = ValueCounts::default();
+
+        for row in values {
+            seen.add(row);
+        }
+
+        for (row, occurrences) in seen.into_counts() {
+            if occurrences < 2 {
+                continue;
+            }
+
+            if count {
+                let mut counted = TaggedDictBuilder::new(name);
+                counted.insert_tagged("value", row);
+                counted.insert("count", Value::int(occurrences as i64));
+                yield ReturnSuccess::value(counted.into_tagged_value());
+            } else {
+                yield ReturnSuccess::value(row);
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
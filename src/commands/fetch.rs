@@ -0,0 +1,116 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{TaggedDictBuilder, Value};
+use crate::prelude::*;
+use mime::Mime;
+use std::str::FromStr;
+use surf::mime;
+
+pub struct Fetch;
+
+#[derive(Deserialize)]
+pub struct FetchArgs {
+    url: Tagged<String>,
+    full: bool,
+}
+
+impl WholeStreamCommand for Fetch {
+    fn name(&self) -> &str {
+        "fetch"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fetch")
+            .required("url", SyntaxType::Any)
+            .switch("full")
+    }
+
+    fn usage(&self) -> &str {
+        "Fetch the body of a url, or its status, headers and body with '--full'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, fetch)?.run()
+    }
+}
+
+// Text, JSON and XML bodies come back as strings, anything else as raw bytes
+fn is_text(content_type: &Mime) -> bool {
+    content_type.type_() == mime::TEXT
+        || content_type.subtype() == mime::JSON
+        || content_type.subtype() == mime::XML
+        || content_type.suffix() == Some(mime::JSON)
+        || content_type.suffix() == Some(mime::XML)
+}
+
+pub async fn fetch_url(url: &Tagged<String>, full: bool) -> Result<Tagged<Value>, ShellError> {
+    let span = url.span();
+
+    if !(url.item.starts_with("http:") || url.item.starts_with("https:")) {
+        return Err(ShellError::labeled_error(
+            "Expected a url",
+            "needs a url starting with http: or https:",
+            span,
+        ));
+    }
+
+    let mut response = surf::get(&url.item)
+        .await
+        .map_err(|e| ShellError::labeled_error("Could not fetch url", format!("{}", e), span))?;
+
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|content_type| Mime::from_str(content_type).ok());
+
+    let body = match content_type {
+        Some(ref content_type) if is_text(content_type) => {
+            Value::string(response.body_string().await.map_err(|e| {
+                ShellError::labeled_error("Could not read the response", format!("{}", e), span)
+            })?)
+        }
+        _ => {
+            let bytes = response.body_bytes().await.map_err(|e| {
+                ShellError::labeled_error("Could not read the response", format!("{}", e), span)
+            })?;
+
+            // Servers that leave out the content type usually send text
+            match (content_type, String::from_utf8(bytes)) {
+                (None, Ok(s)) => Value::string(s),
+                (_, Ok(s)) => Value::Binary(s.into_bytes()),
+                (_, Err(e)) => Value::Binary(e.into_bytes()),
+            }
+        }
+    };
+
+    if !full {
+        return Ok(body.tagged(span));
+    }
+
+    let mut headers = TaggedDictBuilder::new(span);
+    for (name, value) in response.headers().iter() {
+        headers.insert(name, Value::string(value));
+    }
+
+    let mut row = TaggedDictBuilder::new(span);
+    row.insert("status", Value::int(response.status().as_u16()));
+    row.insert_tagged("headers", headers.into_tagged_value());
+    row.insert("body", body);
+
+    Ok(row.into_tagged_value())
+}
+
+fn fetch(
+    FetchArgs { url, full }: FetchArgs,
+    _context: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        yield fetch_url(&url, full).await.map(ReturnSuccess::Value);
+    };
+
+    Ok(stream.to_output_stream())
+}
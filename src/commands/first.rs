@@ -16,7 +16,7 @@ impl WholeStreamCommand for First {
     }
 
     fn usage(&self) -> &str {
-        "Show only the first number of rows."
+        "Show only the first number of rows, or the first share of them given as a percentage like '10%'."
     }
 
     fn run(
@@ -28,23 +28,68 @@ impl WholeStreamCommand for First {
     }
 }
 
+// How many rows to keep: a count, or a share of every row in the stream
+pub enum Amount {
+    Rows(i64),
+    Percent(f64),
+}
+
+impl Amount {
+    pub fn from_value(value: &Tagged<Value>) -> Result<Amount, ShellError> {
+        match &value.item {
+            Value::Primitive(Primitive::String(s)) if s.ends_with('%') => {
+                match s[..s.len() - 1].parse::<f64>() {
+                    Ok(percent) if percent >= 0.0 && percent <= 100.0 => {
+                        Ok(Amount::Percent(percent))
+                    }
+                    _ => Err(ShellError::labeled_error(
+                        "Value is not a percentage",
+                        "expected a percentage from 0% to 100%",
+                        value.span(),
+                    )),
+                }
+            }
+            other => other.as_i64().map(Amount::Rows).map_err(|_| {
+                ShellError::labeled_error(
+                    "Value is not a number",
+                    "expected integer or percentage",
+                    value.span(),
+                )
+            }),
+        }
+    }
+
+    // Percentages round down, so '50%' of 5 rows is 2
+    pub fn of(&self, total: usize) -> usize {
+        match self {
+            Amount::Rows(rows) => *rows as usize,
+            Amount::Percent(percent) => (total as f64 * percent / 100.0).floor() as usize,
+        }
+    }
+}
+
 fn first(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
     let args = args.evaluate_once(registry)?;
 
-    let amount = args.expect_nth(0)?.as_i64();
+    let amount = Amount::from_value(args.expect_nth(0)?)?;
 
-    let amount = match amount {
-        Ok(o) => o,
-        Err(_) => {
-            return Err(ShellError::labeled_error(
-                "Value is not a number",
-                "expected integer",
-                args.expect_nth(0)?.span(),
-            ))
-        }
-    };
+    match amount {
+        Amount::Rows(rows) => Ok(OutputStream::from_input(
+            args.input.values.take(rows as u64),
+        )),
+        // A share of the rows can only be worked out once every row has arrived,
+        // so unlike a count this waits for the whole stream
+        Amount::Percent(_) => {
+            let stream = async_stream_block! {
+                let values: Vec<Tagged<Value>> = args.input.values.collect().await;
+                let rows = amount.of(values.len());
 
-    Ok(OutputStream::from_input(
-        args.input.values.take(amount as u64),
-    ))
+                for value in values.into_iter().take(rows) {
+                    yield ReturnSuccess::value(value);
+                }
+            };
+
+            Ok(stream.to_output_stream())
+        }
+    }
 }
@@ -1,18 +1,25 @@
 use crate::commands::WholeStreamCommand;
 use crate::object::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
-use bson::{decode_document, spec::BinarySubtype, Bson};
+use bson::{decode_document, spec::BinarySubtype, Bson, Document};
+use chrono::SecondsFormat;
 use std::str::FromStr;
 
 pub struct FromBSON;
 
+#[derive(Deserialize)]
+pub struct FromBSONArgs {
+    #[serde(rename(deserialize = "extended-json"))]
+    extended_json: bool,
+}
+
 impl WholeStreamCommand for FromBSON {
     fn name(&self) -> &str {
         "from-bson"
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from-bson")
+        Signature::build("from-bson").switch("extended-json")
     }
 
     fn usage(&self) -> &str {
@@ -24,11 +31,19 @@ impl WholeStreamCommand for FromBSON {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        from_bson(args, registry)
+        args.process(registry, from_bson)?.run()
     }
 }
 
-fn convert_bson_value_to_nu_value(v: &Bson, tag: impl Into<Tag>) -> Tagged<Value> {
+// Datetimes become dates by default. With extended JSON they are kept as
+// {"$date": ...} objects instead, which to-bson turns back into BSON datetimes.
+// Regexes, javascript, timestamps, binary, object ids and symbols are always
+// kept as their `$` objects, so both modes round-trip those through to-bson.
+fn convert_bson_value_to_nu_value(
+    v: &Bson,
+    tag: impl Into<Tag>,
+    extended_json: bool,
+) -> Tagged<Value> {
     let tag = tag.into();
 
     match v {
@@ -36,14 +51,25 @@ fn convert_bson_value_to_nu_value(v: &Bson, tag: impl Into<Tag>) -> Tagged<Value
         Bson::String(s) => Value::Primitive(Primitive::String(String::from(s))).tagged(tag),
         Bson::Array(a) => Value::List(
             a.iter()
-                .map(|x| convert_bson_value_to_nu_value(x, tag))
+                .map(|x| convert_bson_value_to_nu_value(x, tag, extended_json))
                 .collect(),
         )
         .tagged(tag),
         Bson::Document(doc) => {
+            if let Some(decimal) = number_decimal(doc) {
+                return Value::Primitive(Primitive::Decimal(decimal)).tagged(tag);
+            }
+
+            if let Some(number) = number_long(doc) {
+                return Value::Primitive(number).tagged(tag);
+            }
+
             let mut collected = TaggedDictBuilder::new(tag);
             for (k, v) in doc.iter() {
-                collected.insert_tagged(k.clone(), convert_bson_value_to_nu_value(v, tag));
+                collected.insert_tagged(
+                    k.clone(),
+                    convert_bson_value_to_nu_value(v, tag, extended_json),
+                );
             }
 
             collected.into_tagged_value()
@@ -85,7 +111,7 @@ fn convert_bson_value_to_nu_value(v: &Bson, tag: impl Into<Tag>) -> Tagged<Value
             );
             collected.insert_tagged(
                 "$scope".to_string(),
-                convert_bson_value_to_nu_value(&Bson::Document(doc.to_owned()), tag),
+                convert_bson_value_to_nu_value(&Bson::Document(doc.to_owned()), tag, extended_json),
             );
             collected.into_tagged_value()
         }
@@ -121,6 +147,17 @@ fn convert_bson_value_to_nu_value(v: &Bson, tag: impl Into<Tag>) -> Tagged<Value
             );
             collected.into_tagged_value()
         }
+        Bson::UtcDatetime(dt) if extended_json => {
+            let mut collected = TaggedDictBuilder::new(tag);
+            collected.insert_tagged(
+                "$date".to_string(),
+                Value::Primitive(Primitive::String(
+                    dt.to_rfc3339_opts(SecondsFormat::Millis, true),
+                ))
+                .tagged(tag),
+            );
+            collected.into_tagged_value()
+        }
         Bson::UtcDatetime(dt) => Value::Primitive(Primitive::Date(*dt)).tagged(tag),
         Bson::Symbol(s) => {
             let mut collected = TaggedDictBuilder::new(tag);
@@ -133,6 +170,35 @@ fn convert_bson_value_to_nu_value(v: &Bson, tag: impl Into<Tag>) -> Tagged<Value
     }
 }
 
+// A document holding only `$numberDecimal` is how to-bson writes decimals
+fn number_decimal(doc: &Document) -> Option<Decimal> {
+    if doc.len() != 1 {
+        return None;
+    }
+
+    match doc.get("$numberDecimal") {
+        Some(Bson::String(s)) => Decimal::from_str(s).ok(),
+        _ => None,
+    }
+}
+
+// `$numberLong` holds an integer as text. Ones too wide for an i64, as to-bson
+// writes them, come back as whole decimals.
+fn number_long(doc: &Document) -> Option<Primitive> {
+    if doc.len() != 1 {
+        return None;
+    }
+
+    match doc.get("$numberLong") {
+        Some(Bson::String(s)) => match (s.parse::<i64>(), Decimal::from_str(s)) {
+            (Ok(i), _) => Some(Primitive::Int(i)),
+            (_, Ok(d)) if d.trunc() == d => Some(Primitive::Decimal(d)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn binary_subtype_to_string(bst: BinarySubtype) -> String {
     match bst {
         BinarySubtype::Generic => "generic",
@@ -173,28 +239,41 @@ impl std::io::Read for BytesReader {
 pub fn from_bson_bytes_to_value(
     bytes: Vec<u8>,
     tag: impl Into<Tag>,
+    extended_json: bool,
 ) -> bson::DecoderResult<Tagged<Value>> {
     let mut docs = Vec::new();
     let mut b_reader = BytesReader::new(bytes);
     while let Ok(v) = decode_document(&mut b_reader) {
         docs.push(Bson::Document(v));
     }
-    Ok(convert_bson_value_to_nu_value(&Bson::Array(docs), tag))
+    Ok(convert_bson_value_to_nu_value(
+        &Bson::Array(docs),
+        tag,
+        extended_json,
+    ))
 }
 
-fn from_bson(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let span = args.name_span();
-    let input = args.input;
+fn from_bson(
+    FromBSONArgs { extended_json }: FromBSONArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let span = name;
 
     let stream = async_stream_block! {
         let values: Vec<Tagged<Value>> = input.values.collect().await;
 
         for value in values {
             let value_tag = value.tag();
-            match value.item {
-                Value::Binary(vb) =>
-                    match from_bson_bytes_to_value(vb, span) {
+            // A file whose bytes happen to be valid UTF-8 is opened as text
+            let bytes = match value.item {
+                Value::Binary(vb) => Some(vb),
+                Value::Primitive(Primitive::String(s)) => Some(s.into_bytes()),
+                _ => None,
+            };
+
+            match bytes {
+                Some(vb) =>
+                    match from_bson_bytes_to_value(vb, span, extended_json) {
                         Ok(x) => yield ReturnSuccess::value(x),
                         Err(_) => {
                             yield Err(ShellError::labeled_error_with_secondary(
@@ -206,7 +285,7 @@ fn from_bson(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStre
                             ))
                         }
                     }
-                _ => yield Err(ShellError::labeled_error_with_secondary(
+                None => yield Err(ShellError::labeled_error_with_secondary(
                     "Expected a string from pipeline",
                     "requires string input",
                     span,
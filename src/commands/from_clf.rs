@@ -0,0 +1,123 @@
+use crate::commands::WholeStreamCommand;
+use crate::object::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+pub struct FromCLF;
+
+#[derive(Deserialize)]
+pub struct FromCLFArgs {
+    #[serde(rename(deserialize = "skip-errors"))]
+    skip_errors: bool,
+}
+
+impl WholeStreamCommand for FromCLF {
+    fn name(&self) -> &str {
+        "from-clf"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-clf").switch("skip-errors")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse Apache/Nginx access log lines and create a table with a row for each request."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_clf)?.run()
+    }
+}
+
+// The combined format is the common format with the referer and user agent
+// added at the end, so both are optional here
+const CLF_PATTERN: &str =
+    r#"^(\S+) \S+ \S+ \[([^\]]+)\] "([^"]*)" (\d{3}) (\d+|-)(?: "([^"]*)" "([^"]*)")?\s*$"#;
+
+pub fn from_clf_line_to_value(
+    pattern: &Regex,
+    line: &str,
+    tag: impl Into<Tag>,
+) -> Option<Tagged<Value>> {
+    let captures = pattern.captures(line)?;
+
+    let time = DateTime::parse_from_str(&captures[2], "%d/%b/%Y:%H:%M:%S %z").ok()?;
+    let status = captures[4].parse::<i64>().ok()?;
+
+    // A response without a body is logged as '-'
+    let bytes = match &captures[5] {
+        "-" => 0,
+        bytes => bytes.parse::<u64>().ok()?,
+    };
+
+    let optional = |index: usize| match captures.get(index) {
+        Some(value) => Value::string(value.as_str()),
+        None => Value::nothing(),
+    };
+
+    let mut row = TaggedDictBuilder::new(tag);
+    row.insert("remote_host", Value::string(&captures[1]));
+    row.insert(
+        "time",
+        Value::Primitive(Primitive::Date(time.with_timezone(&Utc))),
+    );
+    row.insert("request", Value::string(&captures[3]));
+    row.insert("status", Value::int(status));
+    row.insert("bytes", Value::bytes(bytes));
+    row.insert("referer", optional(6));
+    row.insert("user_agent", optional(7));
+
+    Some(row.into_tagged_value())
+}
+
+fn from_clf(
+    FromCLFArgs { skip_errors }: FromCLFArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let span = name;
+
+    let stream = async_stream_block! {
+        let pattern = Regex::new(CLF_PATTERN).unwrap();
+        let mut values = input.values;
+
+        while let Some(value) = values.next().await {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    for line in s.lines() {
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+
+                        match from_clf_line_to_value(&pattern, line, value_tag) {
+                            Some(row) => yield ReturnSuccess::value(row),
+                            None if skip_errors => {}
+                            None => {
+                                yield Err(ShellError::labeled_error_with_secondary(
+                                    "Could not parse as a common log line",
+                                    "input cannot be parsed as an access log",
+                                    span,
+                                    "value originates from here",
+                                    value_tag.span,
+                                ));
+                                return;
+                            }
+                        }
+                    }
+                }
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
+                    span,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -1,13 +1,31 @@
 use crate::commands::WholeStreamCommand;
 use crate::object::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
+use chrono::{DateTime, NaiveDate, Utc};
 use csv::ReaderBuilder;
+use encoding_rs::{Encoding, UTF_8};
+use std::str::FromStr;
 
 pub struct FromCSV;
 
 #[derive(Deserialize)]
 pub struct FromCSVArgs {
     headerless: bool,
+    #[serde(rename(deserialize = "keep-header-row"))]
+    keep_header_row: bool,
+    separator: Option<Tagged<String>>,
+    #[serde(rename(deserialize = "skip-rows"))]
+    skip_rows: Option<Tagged<i64>>,
+    #[serde(rename(deserialize = "max-rows"))]
+    max_rows: Option<Tagged<i64>>,
+    encoding: Option<Tagged<String>>,
+    strict: bool,
+    comment: Option<Tagged<String>>,
+    schema: Option<Tagged<String>>,
+    #[serde(rename(deserialize = "with-line-numbers"))]
+    with_line_numbers: bool,
+    #[serde(rename(deserialize = "line-column"))]
+    line_column: Option<Tagged<String>>,
 }
 
 impl WholeStreamCommand for FromCSV {
@@ -18,6 +36,16 @@ impl WholeStreamCommand for FromCSV {
     fn signature(&self) -> Signature {
         Signature::build("from-csv")
             .switch("headerless")
+            .switch("keep-header-row")
+            .named("separator", SyntaxType::Any)
+            .named("skip-rows", SyntaxType::Number)
+            .named("max-rows", SyntaxType::Number)
+            .named("encoding", SyntaxType::Any)
+            .switch("strict")
+            .named("comment", SyntaxType::Any)
+            .named("schema", SyntaxType::Any)
+            .switch("with-line-numbers")
+            .named("line-column", SyntaxType::Any)
     }
 
     fn usage(&self) -> &str {
@@ -33,13 +61,223 @@ impl WholeStreamCommand for FromCSV {
     }
 }
 
+const SNIFFED_SEPARATORS: [u8; 4] = [b',', b'\t', b';', b'|'];
+const SNIFFED_LINES: usize = 10;
+
+// A separator is a likely match when it splits each of the first few lines
+// into the same number of fields, and into more than one
+fn splits_consistently(s: &str, separator: u8) -> bool {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(separator)
+        .from_reader(s.as_bytes());
+
+    let mut counts = reader
+        .records()
+        .take(SNIFFED_LINES)
+        .map(|record| record.map(|r| r.len()));
+
+    match counts.next() {
+        Some(Ok(first)) if first > 1 => counts.all(|count| match count {
+            Ok(count) => count == first,
+            Err(_) => false,
+        }),
+        _ => false,
+    }
+}
+
+pub fn sniff_separators(s: &str) -> Vec<u8> {
+    SNIFFED_SEPARATORS
+        .iter()
+        .cloned()
+        .filter(|separator| splits_consistently(s, *separator))
+        .collect()
+}
+
+// Raw bytes are decoded with the given encoding (UTF-8 unless told otherwise).
+// Invalid sequences become U+FFFD, or fail the decode entirely when strict.
+fn decode_bytes(bytes: &[u8], encoding: &'static Encoding, strict: bool) -> Option<String> {
+    if strict {
+        encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|s| s.into_owned())
+    } else {
+        Some(encoding.decode(bytes).0.into_owned())
+    }
+}
+
+// Without an --encoding to go by, bytes holding NULs or invalid UTF-8 are most
+// likely a binary file rather than text in some other encoding. --strict keeps
+// reporting the failed UTF-8 decode instead.
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes.contains(&0) || std::str::from_utf8(bytes).is_err()
+}
+
+#[derive(Clone, Copy)]
+enum ColumnType {
+    String,
+    Int,
+    Decimal,
+    Bool,
+    Date,
+}
+
+impl ColumnType {
+    fn name(self) -> &'static str {
+        match self {
+            ColumnType::String => "string",
+            ColumnType::Int => "int",
+            ColumnType::Decimal => "decimal",
+            ColumnType::Bool => "bool",
+            ColumnType::Date => "date",
+        }
+    }
+
+    // Dates are either RFC 3339 or a plain day, which is taken as midnight UTC
+    fn coerce(self, cell: &str) -> Option<Value> {
+        let cell = cell.trim();
+
+        match self {
+            ColumnType::String => Some(Value::string(cell)),
+            ColumnType::Int => cell.parse::<i64>().ok().map(Value::int),
+            ColumnType::Decimal => Decimal::from_str(cell).ok().map(Value::decimal),
+            ColumnType::Bool => match cell {
+                "true" => Some(Value::boolean(true)),
+                "false" => Some(Value::boolean(false)),
+                _ => None,
+            },
+            ColumnType::Date => match Value::date_from_str(cell) {
+                Ok(date) => Some(date),
+                Err(_) => NaiveDate::parse_from_str(cell, "%Y-%m-%d").ok().map(|day| {
+                    Value::Primitive(Primitive::Date(DateTime::<Utc>::from_utc(
+                        day.and_hms(0, 0, 0),
+                        Utc,
+                    )))
+                }),
+            },
+        }
+    }
+}
+
+// A schema names columns and the type each holds, eg) name:string,age:int.
+// Columns it doesn't name stay strings.
+fn schema_from(schema: Option<Tagged<String>>) -> Result<Vec<(String, ColumnType)>, ShellError> {
+    let schema = match schema {
+        Some(schema) => schema,
+        None => return Ok(vec![]),
+    };
+
+    schema
+        .item
+        .split(',')
+        .map(|column| {
+            let mut parts = column.splitn(2, ':').map(str::trim);
+            let name = parts.next().unwrap_or("");
+            let column_type = match parts.next() {
+                Some("string") => ColumnType::String,
+                Some("int") => ColumnType::Int,
+                Some("decimal") => ColumnType::Decimal,
+                Some("bool") => ColumnType::Bool,
+                Some("date") => ColumnType::Date,
+                _ => {
+                    return Err(ShellError::labeled_error(
+                        format!("Could not read the schema for '{}'", name),
+                        "expected a type of string, int, decimal, bool or date",
+                        schema.span(),
+                    ))
+                }
+            };
+
+            Ok((name.to_string(), column_type))
+        })
+        .collect()
+}
+
+// Returns the column and cell that could not be read as their type
+fn apply_schema(
+    row: Tagged<Value>,
+    schema: &[(String, ColumnType)],
+) -> Result<Tagged<Value>, (String, ColumnType, String)> {
+    if schema.is_empty() {
+        return Ok(row);
+    }
+
+    let tag = row.tag();
+    let mut typed = TaggedDictBuilder::new(tag);
+
+    if let Value::Object(o) = &row.item {
+        for (column, cell) in o.entries.iter() {
+            let column_type = schema
+                .iter()
+                .find(|(name, _)| name == column)
+                .map(|(_, column_type)| *column_type);
+
+            match (column_type, &cell.item) {
+                (Some(column_type), Value::Primitive(Primitive::String(s))) => {
+                    match column_type.coerce(s) {
+                        Some(value) => typed.insert(column.clone(), value),
+                        None => return Err((column.clone(), column_type, s.clone())),
+                    }
+                }
+                _ => typed.insert_tagged(column.clone(), cell.clone()),
+            }
+        }
+    }
+
+    Ok(typed.into_tagged_value())
+}
+
+// Skips the preamble and drops comment lines, which may be indented, along
+// with where each remaining line was in the input
+fn kept_lines<'a>(
+    text: &'a str,
+    skip_rows: usize,
+    comment: Option<char>,
+) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    text.lines()
+        .enumerate()
+        .skip(skip_rows)
+        .filter(move |(_, line)| comment.map_or(true, |c| !line.trim_start().starts_with(c)))
+        .map(|(index, line)| (index + 1, line))
+}
+
+fn strip_lines(text: &str, skip_rows: usize, comment: Option<char>) -> String {
+    kept_lines(text, skip_rows, comment)
+        .map(|(_, line)| format!("{}\n", line))
+        .collect()
+}
+
+// Once a record has started after the header and the first `max_rows`, those
+// are all complete and nothing more needs to be read
+fn holds_rows(text: &str, skip_rows: usize, comment: Option<char>, max_rows: usize) -> bool {
+    let text = strip_lines(text, skip_rows, comment);
+
+    ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+        .records()
+        .take(max_rows + 2)
+        .count()
+        > max_rows + 1
+}
+
 pub fn from_csv_string_to_value(
     s: String,
     headerless: bool,
+    keep_header_row: bool,
+    separator: u8,
+    max_rows: Option<usize>,
+    line_column: Option<(&str, &[usize])>,
     tag: impl Into<Tag>,
 ) -> Result<Tagged<Value>, csv::Error> {
+    // Excel likes to start its exports with a byte order mark
+    let s = s.trim_start_matches('\u{feff}');
+
     let mut reader = ReaderBuilder::new()
         .has_headers(false)
+        .delimiter(separator)
         .from_reader(s.as_bytes());
     let tag = tag.into();
 
@@ -47,6 +285,8 @@ pub fn from_csv_string_to_value(
     let mut iter = reader.records();
     let mut rows = vec![];
 
+    let mut first_row = None;
+
     if let Some(result) = iter.next() {
         let line = result?;
 
@@ -57,10 +297,21 @@ pub fn from_csv_string_to_value(
                 fields.push_back(item.to_string());
             }
         }
+
+        // The first line still names the columns (or just counts them when
+        // headerless), but is also kept as the first row of data
+        if keep_header_row {
+            first_row = Some(line);
+        }
     }
 
     loop {
-        if let Some(row_values) = iter.next() {
+        // Records are read one at a time, so the rest of the input is never parsed
+        if max_rows.map_or(false, |max_rows| rows.len() >= max_rows) {
+            break;
+        }
+
+        if let Some(row_values) = first_row.take().map(Ok).or_else(|| iter.next()) {
             let row_values = row_values?;
 
             let mut row = TaggedDictBuilder::new(tag);
@@ -72,6 +323,18 @@ pub fn from_csv_string_to_value(
                 );
             }
 
+            // Records spanning several lines are numbered by the line they start on
+            if let Some((column, line_numbers)) = line_column {
+                let line = row_values
+                    .position()
+                    .and_then(|position| line_numbers.get(position.line() as usize - 1));
+
+                match line {
+                    Some(line) => row.insert(column, Value::int(*line as i64)),
+                    None => row.insert(column, Value::nothing()),
+                }
+            }
+
             rows.push(row.into_tagged_value());
         } else {
             break;
@@ -84,41 +347,219 @@ pub fn from_csv_string_to_value(
 fn from_csv(
     FromCSVArgs {
         headerless: skip_headers,
+        keep_header_row,
+        separator,
+        skip_rows,
+        max_rows,
+        encoding,
+        strict,
+        comment,
+        schema,
+        with_line_numbers,
+        line_column,
     }: FromCSVArgs,
-    RunnableContext { input, name, .. }: RunnableContext,
+    RunnableContext {
+        input, name, host, ..
+    }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_span = name;
 
+    let separator = match separator {
+        Some(Tagged { item, tag }) => match item.as_bytes() {
+            [separator] => Some(*separator),
+            _ => {
+                return Err(ShellError::labeled_error(
+                    "Expected a single character separator",
+                    "requires a single character",
+                    tag.span,
+                ))
+            }
+        },
+        None => None,
+    };
+
+    let skip_rows = match skip_rows {
+        Some(Tagged { item, tag }) if item < 0 => {
+            return Err(ShellError::labeled_error(
+                "Can not skip a negative number of rows",
+                "expected zero or more rows",
+                tag.span,
+            ))
+        }
+        Some(Tagged { item, .. }) => item as usize,
+        None => 0,
+    };
+
+    let max_rows = match max_rows {
+        Some(Tagged { item, tag }) if item < 0 => {
+            return Err(ShellError::labeled_error(
+                "Can not read a negative number of rows",
+                "expected zero or more rows",
+                tag.span,
+            ))
+        }
+        Some(Tagged { item, .. }) => Some(item as usize),
+        None => None,
+    };
+
+    let comment = match comment {
+        Some(Tagged { item, tag }) => {
+            let mut chars = item.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => {
+                    return Err(ShellError::labeled_error(
+                        "Expected a single character to start comments",
+                        "requires a single character",
+                        tag.span,
+                    ))
+                }
+            }
+        }
+        None => None,
+    };
+
+    let line_column = match line_column {
+        Some(column) => Some(column.item),
+        None if with_line_numbers => Some("_line".to_string()),
+        None => None,
+    };
+
+    let schema_span = schema.as_ref().map(|schema| schema.span());
+    let schema = schema_from(schema)?;
+
+    let guessing_encoding = encoding.is_none();
+
+    let encoding = match encoding {
+        Some(Tagged { item, tag }) => match Encoding::for_label(item.trim().as_bytes()) {
+            Some(encoding) => encoding,
+            None => {
+                return Err(ShellError::labeled_error(
+                    format!("Unknown encoding '{}'", item),
+                    "expected an encoding such as utf-8 or latin1",
+                    tag.span,
+                ))
+            }
+        },
+        None => UTF_8,
+    };
+
     let stream = async_stream_block! {
-        let values: Vec<Tagged<Value>> = input.values.collect().await;
+        let mut values = input.values;
 
         let mut concat_string = String::new();
+        let mut lines_read = 0;
         let mut latest_tag: Option<Tag> = None;
 
-        for value in values {
+        while let Some(value) = values.next().await {
             let value_tag = value.tag();
             latest_tag = Some(value_tag);
             match value.item {
                 Value::Primitive(Primitive::String(s)) => {
                     concat_string.push_str(&s);
                     concat_string.push_str("\n");
+                    lines_read += s.lines().count();
                 }
-                _ => yield Err(ShellError::labeled_error_with_secondary(
-                    "Expected a string from pipeline",
-                    "requires string input",
+                Value::Binary(ref bytes) if guessing_encoding && !strict && looks_binary(bytes) => {
+                    yield Err(ShellError::labeled_error_with_secondary(
+                        "Expected text, found binary data",
+                        "decode it first, eg) from-csv --encoding latin1",
+                        name_span,
+                        "binary data originates from here",
+                        value_tag.span,
+                    ))
+                }
+                Value::Binary(bytes) => match decode_bytes(&bytes, encoding, strict) {
+                    Some(s) => {
+                        concat_string.push_str(&s);
+                        concat_string.push_str("\n");
+                        lines_read += s.lines().count();
+                    }
+                    None => {
+                        let error = ShellError::labeled_error_with_secondary(
+                            format!("Could not decode as {}", encoding.name()),
+                            "input contains invalid bytes",
+                            name_span,
+                            "value originates from here",
+                            value_tag.span,
+                        );
+                        yield Err(error)
+                    }
+                },
+                _ => yield Err(ShellError::type_mismatch(
+                    "string or binary input",
+                    value.tagged_type_name(),
                     name_span,
-                    "value originates from here",
-                    value_tag.span,
                 )),
 
             }
+
+            // With --max-rows, the rest of the input is left unread once it holds
+            // enough rows. Records can span lines, so counting lines only rules
+            // out checking too early
+            if let Some(max_rows) = max_rows {
+                if lines_read > skip_rows + max_rows + 1
+                    && holds_rows(&concat_string, skip_rows, comment, max_rows)
+                {
+                    break;
+                }
+            }
         }
 
-        match from_csv_string_to_value(concat_string, skip_headers, name_span) {
+        // Where each line left to parse was in the input, for --with-line-numbers
+        let line_numbers: Vec<usize> = match line_column {
+            Some(_) => kept_lines(&concat_string, skip_rows, comment)
+                .map(|(number, _)| number)
+                .collect(),
+            None => vec![],
+        };
+
+        let concat_string = if skip_rows > 0 || comment.is_some() {
+            strip_lines(&concat_string, skip_rows, comment)
+        } else {
+            concat_string
+        };
+
+        let separator = match separator {
+            Some(separator) => separator,
+            None => match sniff_separators(&concat_string).as_slice() {
+                [separator] => *separator,
+                [] => b',',
+                _ => {
+                    host.lock().unwrap().stderr(
+                        "from-csv could not tell which separator is used, assuming ','",
+                    );
+                    b','
+                }
+            },
+        };
+
+        match from_csv_string_to_value(
+            concat_string,
+            skip_headers,
+            keep_header_row,
+            separator,
+            max_rows,
+            line_column
+                .as_ref()
+                .map(|column| (column.as_str(), line_numbers.as_slice())),
+            name_span,
+        ) {
             Ok(x) => match x {
                 Tagged { item: Value::List(list), .. } => {
-                    for l in list {
-                        yield ReturnSuccess::value(l);
+                    for (index, l) in list.into_iter().enumerate() {
+                        match apply_schema(l, &schema) {
+                            Ok(row) => yield ReturnSuccess::value(row),
+                            Err((column, column_type, cell)) => {
+                                let error = ShellError::labeled_error(
+                                    format!("Could not read {:?} as {}", cell, column_type.name()),
+                                    format!("row {}, column '{}'", index + 1, column),
+                                    schema_span.unwrap_or(name_span),
+                                );
+                                yield Err(error);
+                                return;
+                            }
+                        }
                     }
                 }
                 x => yield ReturnSuccess::value(x),
@@ -137,3 +578,26 @@ fn from_csv(
 
     Ok(stream.to_output_stream())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FromCSV;
+    use crate::commands::testing::run_command;
+    use crate::object::Value;
+    use crate::prelude::*;
+
+    #[test]
+    fn refuses_binary_input_without_an_encoding() {
+        let input = vec![Value::Binary(vec![0x89, b'P', b'N', b'G', 0, 0]).tagged_unknown()];
+
+        let returned = run_command(FromCSV, &[], input, &CommandRegistry::empty());
+
+        match returned.into_iter().next() {
+            Some(Err(e)) => assert_eq!(
+                e.to_diagnostic().message,
+                "Expected text, found binary data"
+            ),
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+}
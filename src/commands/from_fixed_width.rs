@@ -0,0 +1,188 @@
+use crate::commands::WholeStreamCommand;
+use crate::object::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+
+pub struct FromFixedWidth;
+
+#[derive(Deserialize)]
+pub struct FromFixedWidthArgs {
+    rest: Vec<Tagged<i64>>,
+    headerless: bool,
+}
+
+impl WholeStreamCommand for FromFixedWidth {
+    fn name(&self) -> &str {
+        "from-fixed-width"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-fixed-width")
+            .switch("headerless")
+            .rest(SyntaxType::Number)
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text with fixed-width columns and create table."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_fixed_width)?.run()
+    }
+}
+
+// A column starts wherever a non-blank character follows a blank one in the header
+fn detect_boundaries(header: &str) -> Vec<usize> {
+    let mut boundaries = vec![];
+    let mut previous_was_blank = true;
+
+    for (idx, c) in header.chars().enumerate() {
+        if !c.is_whitespace() && previous_was_blank {
+            boundaries.push(idx);
+        }
+        previous_was_blank = c.is_whitespace();
+    }
+
+    if let Some(first) = boundaries.first_mut() {
+        *first = 0;
+    }
+
+    boundaries
+}
+
+fn slice_fields(line: &str, boundaries: &[usize]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+
+    boundaries
+        .iter()
+        .enumerate()
+        .map(|(idx, start)| {
+            let start = (*start).min(chars.len());
+            let end = match boundaries.get(idx + 1) {
+                Some(end) => (*end).min(chars.len()),
+                None => chars.len(),
+            };
+
+            chars[start..end]
+                .iter()
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
+pub fn from_fixed_width_string_to_value(
+    s: String,
+    boundaries: &[usize],
+    headerless: bool,
+    tag: impl Into<Tag>,
+) -> Tagged<Value> {
+    let tag = tag.into();
+    let mut lines = s.lines().filter(|l| l.trim() != "").peekable();
+
+    let boundaries = match (boundaries.len(), lines.peek()) {
+        (0, Some(header)) => detect_boundaries(header),
+        _ => boundaries.to_vec(),
+    };
+
+    let fields: Vec<String> = if headerless {
+        (0..boundaries.len())
+            .map(|idx| format!("Column{}", idx + 1))
+            .collect()
+    } else {
+        match lines.next() {
+            Some(header) => slice_fields(header, &boundaries),
+            None => vec![],
+        }
+    };
+
+    let mut rows = vec![];
+
+    for line in lines {
+        let mut row = TaggedDictBuilder::new(tag);
+
+        for (field, entry) in fields.iter().zip(slice_fields(line, &boundaries)) {
+            row.insert_tagged(
+                field.clone(),
+                Value::Primitive(Primitive::String(entry)).tagged(tag),
+            );
+        }
+
+        rows.push(row.into_tagged_value());
+    }
+
+    Tagged::from_item(Value::List(rows), tag)
+}
+
+fn from_fixed_width(
+    FromFixedWidthArgs {
+        rest: positions,
+        headerless,
+    }: FromFixedWidthArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_span = name;
+
+    let mut boundaries: Vec<usize> = vec![];
+
+    for position in positions.iter() {
+        if position.item < 0 {
+            return Err(ShellError::labeled_error(
+                "Column positions can't be negative",
+                "expected a position of 0 or more",
+                position.span(),
+            ));
+        }
+
+        let increasing = match boundaries.last() {
+            Some(last) => position.item > *last as i64,
+            None => true,
+        };
+
+        if !increasing {
+            return Err(ShellError::labeled_error(
+                "Column positions must be increasing",
+                "expected a position after the previous column",
+                position.span(),
+            ));
+        }
+
+        boundaries.push(position.item as usize);
+    }
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+
+        for value in values {
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
+                    name_span,
+                )),
+
+            }
+        }
+
+        match from_fixed_width_string_to_value(concat_string, &boundaries, headerless, name_span) {
+            Tagged { item: Value::List(list), .. } => {
+                for l in list {
+                    yield ReturnSuccess::value(l);
+                }
+            }
+            x => yield ReturnSuccess::value(x),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
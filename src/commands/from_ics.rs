@@ -0,0 +1,190 @@
+use crate::commands::from_vcf::{unescape, unfold_lines};
+use crate::commands::WholeStreamCommand;
+use crate::object::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use indexmap::IndexMap;
+
+pub struct FromICS;
+
+#[derive(Deserialize)]
+pub struct FromICSArgs {
+    all: bool,
+}
+
+impl WholeStreamCommand for FromICS {
+    fn name(&self) -> &str {
+        "from-ics"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-ics").switch("all")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .ics and create table with a row for each event, or each component with '--all'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_ics)?.run()
+    }
+}
+
+const DATE_PROPERTIES: [&str; 8] = [
+    "dtstart",
+    "dtend",
+    "dtstamp",
+    "due",
+    "created",
+    "last-modified",
+    "completed",
+    "recurrence-id",
+];
+
+// Times ending in Z are UTC. Floating times, and those with a TZID, are read
+// as local time. Plain dates start at local midnight.
+fn parse_ics_date(value: &str) -> Option<DateTime<Utc>> {
+    if value.ends_with('Z') {
+        return NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+            .ok()
+            .map(|naive| Utc.from_utc_datetime(&naive));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y%m%d").map(|date| date.and_hms(0, 0, 0)))
+        .ok()?;
+
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .map(|local| local.with_timezone(&Utc))
+}
+
+struct Component {
+    name: String,
+    properties: IndexMap<String, Vec<Tagged<Value>>>,
+}
+
+impl Component {
+    // Properties given more than once, like several attendees, become lists
+    fn into_tagged_value(self, all: bool, tag: Tag) -> Tagged<Value> {
+        let mut row = TaggedDictBuilder::new(tag);
+
+        if all {
+            row.insert("component", Value::string(self.name.to_lowercase()));
+        }
+
+        for (name, mut values) in self.properties {
+            match values.len() {
+                1 => row.insert_tagged(name, values.remove(0)),
+                _ => row.insert(name, Value::List(values)),
+            }
+        }
+
+        row.into_tagged_value()
+    }
+}
+
+pub fn from_ics_string_to_value(s: String, all: bool, tag: impl Into<Tag>) -> Tagged<Value> {
+    let tag = tag.into();
+    let mut rows = vec![];
+
+    // Components nest, eg) an alarm inside an event, so each keeps its own properties
+    let mut open: Vec<Component> = vec![];
+
+    for line in unfold_lines(&s) {
+        let colon = match line.find(':') {
+            Some(colon) => colon,
+            None => continue,
+        };
+
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let name = head.split(';').next().unwrap_or("").trim().to_lowercase();
+
+        match name.as_str() {
+            "begin" => open.push(Component {
+                name: value.trim().to_uppercase(),
+                properties: IndexMap::new(),
+            }),
+            "end" => {
+                if let Some(component) = open.pop() {
+                    let wanted = match component.name.as_str() {
+                        "VEVENT" => true,
+                        "VCALENDAR" => false,
+                        _ => all,
+                    };
+
+                    if wanted {
+                        rows.push(component.into_tagged_value(all, tag));
+                    }
+                }
+            }
+            "" => {}
+            _ => {
+                if let Some(component) = open.last_mut() {
+                    let parsed = if DATE_PROPERTIES.contains(&name.as_str()) {
+                        parse_ics_date(value.trim()).map(Primitive::Date)
+                    } else {
+                        None
+                    };
+
+                    let value = match parsed {
+                        Some(date) => Value::Primitive(date),
+                        None => Value::string(unescape(value)),
+                    };
+
+                    component
+                        .properties
+                        .entry(name)
+                        .or_insert_with(Vec::new)
+                        .push(value.tagged(tag));
+                }
+            }
+        }
+    }
+
+    Value::List(rows).tagged(tag)
+}
+
+fn from_ics(
+    FromICSArgs { all }: FromICSArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let span = name;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+
+        for value in values {
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
+                    span,
+                )),
+
+            }
+        }
+
+        match from_ics_string_to_value(concat_string, all, span) {
+            Tagged { item: Value::List(list), .. } => {
+                for l in list {
+                    yield ReturnSuccess::value(l);
+                }
+            }
+            x => yield ReturnSuccess::value(x),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
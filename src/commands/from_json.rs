@@ -1,12 +1,15 @@
 use crate::commands::WholeStreamCommand;
 use crate::object::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
+use std::str::FromStr;
 
 pub struct FromJSON;
 
 #[derive(Deserialize)]
 pub struct FromJSONArgs {
     objects: bool,
+    concatenated: bool,
+    pointer: Option<Tagged<String>>,
 }
 
 impl WholeStreamCommand for FromJSON {
@@ -17,6 +20,8 @@ impl WholeStreamCommand for FromJSON {
     fn signature(&self) -> Signature {
         Signature::build("from-json")
             .switch("objects")
+            .switch("concatenated")
+            .named("pointer", SyntaxType::Any)
     }
 
     fn usage(&self) -> &str {
@@ -32,45 +37,253 @@ impl WholeStreamCommand for FromJSON {
     }
 }
 
-fn convert_json_value_to_nu_value(v: &serde_hjson::Value, tag: impl Into<Tag>) -> Tagged<Value> {
+fn convert_json_value_to_nu_value(
+    v: &serde_hjson::Value,
+    tag: impl Into<Tag>,
+    wide: &WideIntegers,
+) -> Result<Tagged<Value>, String> {
     let tag = tag.into();
 
-    match v {
+    Ok(match v {
         serde_hjson::Value::Null => Value::Primitive(Primitive::Nothing).tagged(tag),
         serde_hjson::Value::Bool(b) => Value::Primitive(Primitive::Boolean(*b)).tagged(tag),
-        serde_hjson::Value::F64(n) => Value::Primitive(Primitive::from(*n)).tagged(tag),
+        // Floats stay decimals even when integral, so 1.0 and 1 round-trip apart
+        serde_hjson::Value::F64(n) => {
+            Value::Primitive(Primitive::Decimal(float_decimal(*n)?)).tagged(tag)
+        }
+        // Integers past i64 are kept exactly rather than wrapping around
+        serde_hjson::Value::U64(n) if *n > i64::max_value() as u64 => {
+            Value::Primitive(Primitive::BigInt(BigInt::from(*n))).tagged(tag)
+        }
         serde_hjson::Value::U64(n) => Value::Primitive(Primitive::Int(*n as i64)).tagged(tag),
         serde_hjson::Value::I64(n) => Value::Primitive(Primitive::Int(*n as i64)).tagged(tag),
-        serde_hjson::Value::String(s) => {
-            Value::Primitive(Primitive::String(String::from(s))).tagged(tag)
-        }
+        serde_hjson::Value::String(s) => match wide.get(s) {
+            Some(n) => Value::Primitive(Primitive::BigInt(n.clone())).tagged(tag),
+            None => Value::Primitive(Primitive::String(String::from(s))).tagged(tag),
+        },
         serde_hjson::Value::Array(a) => Value::List(
             a.iter()
-                .map(|x| convert_json_value_to_nu_value(x, tag))
-                .collect(),
+                .map(|x| convert_json_value_to_nu_value(x, tag, wide))
+                .collect::<Result<Vec<_>, _>>()?,
         )
         .tagged(tag),
         serde_hjson::Value::Object(o) => {
             let mut collected = TaggedDictBuilder::new(tag);
             for (k, v) in o.iter() {
-                collected.insert_tagged(k.clone(), convert_json_value_to_nu_value(v, tag));
+                collected.insert_tagged(k.clone(), convert_json_value_to_nu_value(v, tag, wide)?);
             }
 
             collected.into_tagged_value()
         }
+    })
+}
+
+// An integral float keeps a decimal place so it still reads as a float. A
+// decimal tops out near 7.9e28, so larger floats can't be read.
+fn float_decimal(n: f64) -> Result<Decimal, String> {
+    let decimal =
+        Decimal::from_f64(n).ok_or_else(|| format!("{:e} is out of range for a decimal", n))?;
+
+    Ok(match decimal.scale() {
+        0 => decimal.checked_mul(Decimal::new(10, 1)).unwrap_or(decimal),
+        _ => decimal,
+    })
+}
+
+// Hjson panics on an integer literal that doesn't fit in a u64 (or an i64 when
+// negative). Before parsing, each such literal that makes up a whole value is
+// swapped for a quoted placeholder, which is read back as the exact integer.
+struct WideIntegers {
+    marker: String,
+    values: Vec<BigInt>,
+}
+
+impl WideIntegers {
+    fn replace(text: &str) -> (String, WideIntegers) {
+        let mut marker = String::from("__nu_wide_integer_");
+        while text.contains(&marker) {
+            marker.push('_');
+        }
+
+        let mut wide = WideIntegers {
+            marker,
+            values: vec![],
+        };
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        // Whether a value may begin here, ie) after ':', ',', '[' or a line break
+        let mut value_start = true;
+
+        while let Some(c) = rest.chars().next() {
+            let skipped = if rest.starts_with('"') {
+                Some(quoted_len(rest))
+            } else if rest.starts_with("'''") {
+                Some(
+                    rest[3..]
+                        .find("'''")
+                        .map(|end| end + 6)
+                        .unwrap_or_else(|| rest.len()),
+                )
+            } else if rest.starts_with('#') || rest.starts_with("//") {
+                Some(rest.find('\n').unwrap_or_else(|| rest.len()))
+            } else if rest.starts_with("/*") {
+                Some(
+                    rest[2..]
+                        .find("*/")
+                        .map(|end| end + 4)
+                        .unwrap_or_else(|| rest.len()),
+                )
+            } else {
+                None
+            };
+
+            if let Some(len) = skipped {
+                out.push_str(&rest[..len]);
+                rest = &rest[len..];
+                value_start = false;
+                continue;
+            }
+
+            if value_start && (c == '-' || c.is_ascii_digit()) {
+                let len = rest[1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .map(|end| end + 1)
+                    .unwrap_or_else(|| rest.len());
+                let after = rest[len..].trim_start_matches(|c| c == ' ' || c == '\t');
+                let whole_value = after.is_empty()
+                    || after.starts_with(|c| ",]}\r\n#".contains(c))
+                    || after.starts_with("//")
+                    || after.starts_with("/*");
+
+                if let (true, Some(n)) = (whole_value, wide_integer(&rest[..len])) {
+                    out.push_str(&format!("\"{}{}\"", wide.marker, wide.values.len()));
+                    wide.values.push(n);
+                    rest = &rest[len..];
+                    value_start = false;
+                    continue;
+                }
+            }
+
+            match c {
+                ':' | ',' | '[' | '\n' => value_start = true,
+                ' ' | '\t' | '\r' => {}
+                _ => value_start = false,
+            }
+
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+
+        (out, wide)
+    }
+
+    fn get(&self, s: &str) -> Option<&BigInt> {
+        if !s.starts_with(&self.marker) {
+            return None;
+        }
+
+        usize::from_str(&s[self.marker.len()..])
+            .ok()
+            .and_then(|index| self.values.get(index))
     }
 }
 
-pub fn from_json_string_to_value(
-    s: String,
-    tag: impl Into<Tag>,
-) -> serde_hjson::Result<Tagged<Value>> {
-    let v: serde_hjson::Value = serde_hjson::from_str(&s)?;
-    Ok(convert_json_value_to_nu_value(&v, tag))
+// The length of the "..." string that text starts with, escapes included
+fn quoted_len(text: &str) -> usize {
+    let mut escaped = false;
+
+    for (idx, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return idx + 1,
+            _ => {}
+        }
+    }
+
+    text.len()
+}
+
+// An integer literal too wide for Hjson to read
+fn wide_integer(token: &str) -> Option<BigInt> {
+    let digits = token.trim_start_matches('-');
+
+    if digits.is_empty() || (digits.starts_with('0') && digits.len() > 1) {
+        return None;
+    }
+
+    let fits = if token.starts_with('-') {
+        i64::from_str(token).is_ok()
+    } else {
+        u64::from_str(token).is_ok()
+    };
+
+    if fits {
+        None
+    } else {
+        BigInt::from_str(token).ok()
+    }
+}
+
+pub fn from_json_string_to_value(s: String, tag: impl Into<Tag>) -> Result<Tagged<Value>, String> {
+    let (s, wide) = WideIntegers::replace(&s);
+    let v: serde_hjson::Value = serde_hjson::from_str(&s).map_err(|e| e.to_string())?;
+    convert_json_value_to_nu_value(&v, tag, &wide)
+}
+
+// Follows an RFC 6901 JSON Pointer such as /data/items/0. Each token names a
+// key, or an index when the value is a list, with ~1 and ~0 standing for / and ~
+fn select_pointer(
+    value: Tagged<Value>,
+    pointer: &Tagged<String>,
+) -> Result<Tagged<Value>, ShellError> {
+    if pointer.item.is_empty() {
+        return Ok(value);
+    }
+
+    if !pointer.item.starts_with('/') {
+        return Err(ShellError::labeled_error(
+            "Invalid JSON pointer",
+            "a JSON pointer starts with '/'",
+            pointer.span(),
+        ));
+    }
+
+    let mut current = value;
+
+    for token in pointer.item[1..].split('/') {
+        let token = token.replace("~1", "/").replace("~0", "~");
+
+        let next = match &current.item {
+            Value::Object(o) => o.get_data_by_key(&token).cloned(),
+            Value::List(l) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(|idx| l.get(idx).cloned()),
+            _ => None,
+        };
+
+        current = match next {
+            Some(next) => next,
+            None => {
+                return Err(ShellError::labeled_error(
+                    "JSON pointer not found",
+                    format!("nothing at '{}'", token),
+                    pointer.span(),
+                ))
+            }
+        };
+    }
+
+    Ok(current)
 }
 
 fn from_json(
-    FromJSONArgs { objects }: FromJSONArgs,
+    FromJSONArgs {
+        objects,
+        concatenated,
+        pointer,
+    }: FromJSONArgs,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_span = name;
@@ -89,58 +302,89 @@ fn from_json(
                     concat_string.push_str(&s);
                     concat_string.push_str("\n");
                 }
-                _ => yield Err(ShellError::labeled_error_with_secondary(
-                    "Expected a string from pipeline",
-                    "requires string input",
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
                     name_span,
-                    "value originates from here",
-                    value_tag.span,
                 )),
 
             }
         }
 
 
-        if objects {
+        if concatenated {
+            // Values follow one another with or without whitespace between them,
+            // eg) {"a":1}{"b":2}. serde_json only finds where each one ends, and
+            // the text in between is read like any other JSON
+            let mut values = serde_json::Deserializer::from_str(&concat_string)
+                .into_iter::<serde::de::IgnoredAny>();
+            let mut start = 0;
+
+            while let Some(value) = values.next() {
+                let parsed = match value {
+                    Ok(_) => {
+                        let end = values.byte_offset();
+                        let text = concat_string[start..end].to_string();
+                        start = end;
+
+                        from_json_string_to_value(text, name_span)
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+
+                match parsed {
+                    Ok(x) => match &pointer {
+                        Some(pointer) => yield select_pointer(x, pointer).map(ReturnSuccess::Value),
+                        None => yield ReturnSuccess::value(x),
+                    },
+                    Err(e) => {
+                        if let Some(last_tag) = latest_tag {
+                            yield Err(ShellError::parse_failure("JSON", e, name_span, last_tag.span))
+                        }
+                        break;
+                    }
+                }
+            }
+        } else if objects {
             for json_str in concat_string.lines() {
                 if json_str.is_empty() {
                     continue;
                 }
 
                 match from_json_string_to_value(json_str.to_string(), name_span) {
-                    Ok(x) =>
-                        yield ReturnSuccess::value(x),
-                    Err(_) => {
+                    Ok(x) => match &pointer {
+                        Some(pointer) => yield select_pointer(x, pointer).map(ReturnSuccess::Value),
+                        None => yield ReturnSuccess::value(x),
+                    },
+                    Err(e) => {
                         if let Some(last_tag) = latest_tag {
-                            yield Err(ShellError::labeled_error_with_secondary(
-                                "Could nnot parse as JSON",
-                                "input cannot be parsed as JSON",
-                                name_span,
-                                "value originates from here",
-                                last_tag.span))
+                            yield Err(ShellError::parse_failure("JSON", e, name_span, last_tag.span))
                         }
                     }
                 }
             }
         } else {
             match from_json_string_to_value(concat_string, name_span) {
-                Ok(x) =>
-                    match x {
-                        Tagged { item: Value::List(list), .. } => {
+                Ok(x) => {
+                    let selected = match &pointer {
+                        Some(pointer) => select_pointer(x, pointer),
+                        None => Ok(x),
+                    };
+
+                    match selected {
+                        Ok(Tagged { item: Value::List(list), .. }) => {
                             for l in list {
                                 yield ReturnSuccess::value(l);
                             }
                         }
-                        x => yield ReturnSuccess::value(x),
+                        // A bare scalar such as 42 or "ok" is a single row
+                        Ok(x) => yield ReturnSuccess::value(x),
+                        Err(e) => yield Err(e),
                     }
-                Err(_) => {
+                }
+                Err(e) => {
                     if let Some(last_tag) = latest_tag {
-                        yield Err(ShellError::labeled_error_with_secondary(
-                            "Could not parse as JSON",
-                            "input cannot be parsed as JSON",
-                            name_span,
-                            "value originates from here",
-                            last_tag.span))
+                        yield Err(ShellError::parse_failure("JSON", e, name_span, last_tag.span))
                     }
                 }
             }
@@ -149,3 +393,116 @@ fn from_json(
 
     Ok(stream.to_output_stream())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FromJSON;
+    use crate::commands::testing::run_command;
+    use crate::object::{Primitive, Value};
+    use crate::prelude::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn reads_a_row_for_each_line_with_objects() {
+        let input = vec![Value::string("{\"n\": 1}\n{\"n\": 2}").tagged_unknown()];
+
+        let returned = run_command(FromJSON, &["objects"], input, &CommandRegistry::empty());
+
+        let numbers: Vec<Value> = returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(row)) => row
+                    .get_data_by_path(row.tag(), "n")
+                    .map(|n| n.item.clone())
+                    .expect("row without n"),
+                other => panic!("expected a row, got {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(numbers, vec![Value::int(1), Value::int(2)]);
+    }
+
+    #[test]
+    fn reads_values_written_back_to_back_with_concatenated() {
+        let input = vec![Value::string("{\"a\": 1}{\"a\": 2}\n [3]").tagged_unknown()];
+
+        let returned = run_command(
+            FromJSON,
+            &["concatenated"],
+            input,
+            &CommandRegistry::empty(),
+        );
+
+        let values: Vec<Value> = returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(value)) => value.item,
+                other => panic!("expected a value, got {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            values[1].get_data_by_key("a").map(|a| a.item.clone()),
+            Some(Value::int(2))
+        );
+        match &values[2] {
+            Value::List(list) => assert_eq!(list[0].item, Value::int(3)),
+            other => panic!("expected a list, got {:?}", other),
+        }
+    }
+
+    fn read(text: &str) -> Vec<Value> {
+        let input = vec![Value::string(text).tagged_unknown()];
+
+        run_command(FromJSON, &[], input, &CommandRegistry::empty())
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(value)) => value.item,
+                other => panic!("expected a value, got {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_a_bare_scalar_at_the_top_level() {
+        assert_eq!(read("42"), vec![Value::int(42)]);
+        assert_eq!(read("\"ok\""), vec![Value::string("ok")]);
+        assert_eq!(read("true"), vec![Value::boolean(true)]);
+    }
+
+    #[test]
+    fn reads_integers_wider_than_a_u64_exactly() {
+        let wide = "123456789012345678901234567890";
+
+        assert_eq!(
+            read(&format!("[{}, -{}, \"{}\"]", wide, wide, wide)),
+            vec![
+                Value::Primitive(Primitive::BigInt(BigInt::from_str(wide).unwrap())),
+                Value::Primitive(Primitive::BigInt(-BigInt::from_str(wide).unwrap())),
+                Value::string(wide),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_input_that_is_not_text() {
+        let input = vec![Value::int(1).tagged_unknown()];
+
+        let returned = run_command(FromJSON, &[], input, &CommandRegistry::empty());
+
+        assert!(returned[0].is_err());
+    }
+
+    #[test]
+    fn reports_floats_too_large_for_a_decimal() {
+        let input = vec![Value::string("{\"x\": 1e30}").tagged_unknown()];
+
+        let returned = run_command(FromJSON, &[], input, &CommandRegistry::empty());
+
+        match &returned[..] {
+            [Err(e)] => assert!(format!("{:?}", e).contains("1e30 is out of range")),
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::commands::WholeStreamCommand;
+use crate::object::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use std::str::FromStr;
+
+pub struct FromLogfmt;
+
+#[derive(Deserialize)]
+pub struct FromLogfmtArgs {
+    coerce: bool,
+}
+
+impl WholeStreamCommand for FromLogfmt {
+    fn name(&self) -> &str {
+        "from-logfmt"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-logfmt").switch("coerce")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse key=value log lines and create a table with a row for each line."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_logfmt)?.run()
+    }
+}
+
+enum Field {
+    Bare(String),
+    Quoted(String),
+}
+
+// Reads `key=value` pairs separated by spaces. Quoted values may hold spaces
+// and backslash escapes, and a key given without a value is kept as empty.
+fn parse_pairs(line: &str) -> Vec<(String, Field)> {
+    let mut pairs = vec![];
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while let Some(c) = chars.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            chars.next();
+        }
+
+        let mut key = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '=' || c.is_whitespace() {
+                break;
+            }
+            key.push(c);
+            chars.next();
+        }
+
+        if key.is_empty() && chars.peek().is_none() {
+            return pairs;
+        }
+
+        if chars.peek() != Some(&'=') {
+            pairs.push((key, Field::Bare(String::new())));
+            continue;
+        }
+        chars.next();
+
+        let field = match chars.peek() {
+            Some('"') => {
+                chars.next();
+                let mut value = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(other) => value.push(other),
+                            None => value.push('\\'),
+                        },
+                        c => value.push(c),
+                    }
+                }
+                Field::Quoted(value)
+            }
+            _ => {
+                let mut value = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                Field::Bare(value)
+            }
+        };
+
+        pairs.push((key, field));
+    }
+}
+
+fn coerce_number(value: String) -> Value {
+    if let Ok(i) = value.parse::<i64>() {
+        return Value::int(i);
+    }
+
+    match Decimal::from_str(&value) {
+        Ok(d) => Value::Primitive(Primitive::Decimal(d)),
+        Err(_) => Value::string(value),
+    }
+}
+
+pub fn from_logfmt_line_to_value(line: &str, coerce: bool, tag: impl Into<Tag>) -> Tagged<Value> {
+    let mut row = TaggedDictBuilder::new(tag);
+
+    // Lines that hold no pairs at all are kept whole as the message
+    if !line.contains('=') {
+        row.insert("msg", Value::string(line.trim()));
+        return row.into_tagged_value();
+    }
+
+    for (key, field) in parse_pairs(line) {
+        let value = match field {
+            Field::Bare(value) => {
+                if coerce {
+                    coerce_number(value)
+                } else {
+                    Value::string(value)
+                }
+            }
+            Field::Quoted(value) => Value::string(value),
+        };
+
+        row.insert(key, value);
+    }
+
+    row.into_tagged_value()
+}
+
+fn from_logfmt(
+    FromLogfmtArgs { coerce }: FromLogfmtArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let span = name;
+
+    let stream = async_stream_block! {
+        let mut values = input.values;
+
+        while let Some(value) = values.next().await {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    for line in s.lines() {
+                        if !line.trim().is_empty() {
+                            yield ReturnSuccess::value(from_logfmt_line_to_value(line, coerce, value_tag));
+                        }
+                    }
+                }
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
+                    span,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -34,6 +34,8 @@ pub fn convert_toml_value_to_nu_value(v: &toml::Value, tag: impl Into<Tag>) -> T
         toml::Value::Integer(n) => Value::Primitive(Primitive::Int(*n)).tagged(tag),
         toml::Value::Float(n) => Value::Primitive(Primitive::from(*n)).tagged(tag),
         toml::Value::String(s) => Value::Primitive(Primitive::String(String::from(s))).tagged(tag),
+        // An array of tables, eg) [[servers]], arrives here already in file order,
+        // with each [servers.config] nested inside the table it follows
         toml::Value::Array(a) => Value::List(
             a.iter()
                 .map(|x| convert_toml_value_to_nu_value(x, tag))
@@ -96,7 +98,7 @@ pub fn from_toml(
             }
         }
 
-        match from_toml_string_to_value(concat_string, span) {
+        match from_toml_string_to_value(concat_string.clone(), span) {
             Ok(x) => match x {
                 Tagged { item: Value::List(list), .. } => {
                     for l in list {
@@ -105,14 +107,23 @@ pub fn from_toml(
                 }
                 x => yield ReturnSuccess::value(x),
             },
-            Err(_) => if let Some(last_tag) = latest_tag {
-                yield Err(ShellError::labeled_error_with_secondary(
-                    "Could not parse as TOML",
-                    "input cannot be parsed as TOML",
-                    span,
-                    "value originates from here",
-                    last_tag.span,
-                ))
+            Err(e) => if let Some(last_tag) = latest_tag {
+                // Errors can only point into the command line, so spell out where the input went wrong
+                match e.line_col() {
+                    Some((line, col)) => {
+                        let offending = concat_string.lines().nth(line).unwrap_or("").trim();
+
+                        let error = ShellError::labeled_error_with_secondary(
+                            format!("Could not parse as TOML at line {}, column {}", line + 1, col + 1),
+                            e.to_string(),
+                            span,
+                            format!("line {} is `{}`", line + 1, offending),
+                            last_tag.span,
+                        );
+                        yield Err(error)
+                    }
+                    None => yield Err(ShellError::parse_failure("TOML", e, span, last_tag.span)),
+                }
             } ,
         }
     };
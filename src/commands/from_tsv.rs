@@ -37,6 +37,9 @@ pub fn from_tsv_string_to_value(
     headerless: bool,
     tag: impl Into<Tag>,
 ) -> Result<Tagged<Value>, csv::Error> {
+    // Excel likes to start its exports with a byte order mark
+    let s = s.trim_start_matches('\u{feff}');
+
     let mut reader = ReaderBuilder::new()
         .has_headers(false)
         .delimiter(b'\t')
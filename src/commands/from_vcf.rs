@@ -0,0 +1,242 @@
+use crate::commands::WholeStreamCommand;
+use crate::object::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use indexmap::IndexMap;
+
+pub struct FromVCF;
+
+impl WholeStreamCommand for FromVCF {
+    fn name(&self) -> &str {
+        "from-vcf"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-vcf")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .vcf and create table with a row for each contact card."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_vcf(args, registry)
+    }
+}
+
+// Long lines are folded by starting the next line with a space or a tab
+pub(crate) fn unfold_lines(s: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for line in s.lines() {
+        let line = line.trim_end_matches('\r');
+
+        match (
+            line.starts_with(' ') || line.starts_with('\t'),
+            lines.last_mut(),
+        ) {
+            (true, Some(previous)) => previous.push_str(&line[1..]),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+pub(crate) fn unescape(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+// A content line reads `NAME;PARAM=VALUE;...:value`. Groups such as
+// `item1.EMAIL` are dropped, and names come out lowercase.
+fn parse_property(line: &str) -> Option<Property> {
+    let colon = line.find(':')?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.rsplit('.').next()?.trim().to_lowercase();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let params = parts
+        .map(|param| match param.find('=') {
+            Some(eq) => (param[..eq].to_lowercase(), param[eq + 1..].to_string()),
+            // vCard 2.1 allows bare types, eg) TEL;CELL
+            None => ("type".to_string(), param.to_string()),
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: unescape(value),
+    })
+}
+
+struct Card {
+    properties: IndexMap<String, Vec<Tagged<Value>>>,
+    malformed: bool,
+}
+
+impl Card {
+    fn new() -> Card {
+        Card {
+            properties: IndexMap::new(),
+            malformed: false,
+        }
+    }
+
+    fn add(&mut self, property: Property, tag: Tag) {
+        // Parameters such as TYPE=CELL sit next to the value in a nested row
+        let value = if property.params.is_empty() {
+            Value::string(property.value).tagged(tag)
+        } else {
+            let mut params = TaggedDictBuilder::new(tag);
+            for (name, value) in property.params {
+                params.insert(name, Value::string(value));
+            }
+
+            let mut nested = TaggedDictBuilder::new(tag);
+            nested.insert("value", Value::string(property.value));
+            nested.insert_tagged("params", params.into_tagged_value());
+            nested.into_tagged_value()
+        };
+
+        self.properties
+            .entry(property.name)
+            .or_insert_with(Vec::new)
+            .push(value);
+    }
+
+    // Properties given more than once, like several emails, become lists
+    fn into_tagged_value(self, tag: Tag) -> Tagged<Value> {
+        let mut row = TaggedDictBuilder::new(tag);
+
+        for (name, mut values) in self.properties {
+            match values.len() {
+                1 => row.insert_tagged(name, values.remove(0)),
+                _ => row.insert(name, Value::List(values)),
+            }
+        }
+
+        if self.malformed {
+            row.insert("malformed", Primitive::Boolean(true));
+        }
+
+        row.into_tagged_value()
+    }
+}
+
+pub fn from_vcf_string_to_value(s: String, tag: impl Into<Tag>) -> Tagged<Value> {
+    let tag = tag.into();
+    let mut cards = vec![];
+    let mut current: Option<Card> = None;
+
+    for line in unfold_lines(&s) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let upper = line.trim().to_uppercase();
+
+        if upper == "BEGIN:VCARD" {
+            // A card that never ended is kept, but flagged
+            if let Some(mut unfinished) = current.take() {
+                unfinished.malformed = true;
+                cards.push(unfinished.into_tagged_value(tag));
+            }
+            current = Some(Card::new());
+        } else if upper == "END:VCARD" {
+            match current.take() {
+                Some(card) => cards.push(card.into_tagged_value(tag)),
+                None => {
+                    let mut stray = Card::new();
+                    stray.malformed = true;
+                    cards.push(stray.into_tagged_value(tag));
+                }
+            }
+        } else {
+            let card = current.get_or_insert_with(|| Card {
+                properties: IndexMap::new(),
+                malformed: true,
+            });
+
+            match parse_property(&line) {
+                Some(property) => card.add(property, tag),
+                None => card.malformed = true,
+            }
+        }
+    }
+
+    if let Some(mut unfinished) = current.take() {
+        unfinished.malformed = true;
+        cards.push(unfinished.into_tagged_value(tag));
+    }
+
+    Value::List(cards).tagged(tag)
+}
+
+fn from_vcf(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let span = args.name_span();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+
+        for value in values {
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
+                    span,
+                )),
+
+            }
+        }
+
+        match from_vcf_string_to_value(concat_string, span) {
+            Tagged { item: Value::List(list), .. } => {
+                for l in list {
+                    yield ReturnSuccess::value(l);
+                }
+            }
+            x => yield ReturnSuccess::value(x),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
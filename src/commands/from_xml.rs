@@ -1,6 +1,7 @@
 use crate::commands::WholeStreamCommand;
 use crate::object::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
+use indexmap::IndexMap;
 
 pub struct FromXML;
 
@@ -10,7 +11,7 @@ impl WholeStreamCommand for FromXML {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from-xml")
+        Signature::build("from-xml").switch("simple")
     }
 
     fn usage(&self) -> &str {
@@ -69,21 +70,75 @@ fn from_node_to_value<'a, 'd>(n: &roxmltree::Node<'a, 'd>, tag: impl Into<Tag>)
     }
 }
 
-fn from_document_to_value(d: &roxmltree::Document, tag: impl Into<Tag>) -> Tagged<Value> {
-    from_node_to_value(&d.root_element(), tag)
+// The simple form keeps only element names and text, so it reads like JSON.
+// An element holding only text becomes that text, and the children of any
+// other element become the columns of an object, gathered into a list when a
+// name repeats. Attributes, and text sitting between child elements, are dropped.
+fn from_node_to_simple_value<'a, 'd>(n: &roxmltree::Node<'a, 'd>, tag: Tag) -> Tagged<Value> {
+    let elements: Vec<roxmltree::Node> = n.children().filter(|c| c.is_element()).collect();
+
+    if elements.is_empty() {
+        let text: String = n
+            .children()
+            .filter(|c| c.is_text())
+            .filter_map(|c| c.text())
+            .collect();
+        return Value::string(text.trim()).tagged(tag);
+    }
+
+    let mut columns: IndexMap<String, Vec<Tagged<Value>>> = IndexMap::new();
+    for c in elements {
+        columns
+            .entry(c.tag_name().name().trim().to_string())
+            .or_insert_with(Vec::new)
+            .push(from_node_to_simple_value(&c, tag));
+    }
+
+    let mut collected = TaggedDictBuilder::new(tag);
+    for (name, mut values) in columns {
+        if values.len() == 1 {
+            collected.insert_tagged(name, values.remove(0));
+        } else {
+            collected.insert(name, Value::List(values));
+        }
+    }
+
+    collected.into_tagged_value()
+}
+
+fn from_document_to_value(
+    d: &roxmltree::Document,
+    simple: bool,
+    tag: impl Into<Tag>,
+) -> Tagged<Value> {
+    let tag = tag.into();
+    let root = d.root_element();
+
+    if simple {
+        let mut collected = TaggedDictBuilder::new(tag);
+        collected.insert_tagged(
+            root.tag_name().name().trim(),
+            from_node_to_simple_value(&root, tag),
+        );
+        collected.into_tagged_value()
+    } else {
+        from_node_to_value(&root, tag)
+    }
 }
 
 pub fn from_xml_string_to_value(
     s: String,
+    simple: bool,
     tag: impl Into<Tag>,
 ) -> Result<Tagged<Value>, roxmltree::Error> {
     let parsed = roxmltree::Document::parse(&s)?;
-    Ok(from_document_to_value(&parsed, tag))
+    Ok(from_document_to_value(&parsed, simple, tag))
 }
 
 fn from_xml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
     let args = args.evaluate_once(registry)?;
     let span = args.name_span();
+    let simple = args.has("simple");
     let input = args.input;
 
     let stream = async_stream_block! {
@@ -111,7 +166,7 @@ fn from_xml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStrea
             }
         }
 
-        match from_xml_string_to_value(concat_string, span) {
+        match from_xml_string_to_value(concat_string, simple, span) {
             Ok(x) => match x {
                 Tagged { item: Value::List(list), .. } => {
                     for l in list {
@@ -1,9 +1,21 @@
 use crate::commands::WholeStreamCommand;
 use crate::object::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use uuid::Uuid;
+use yaml_rust::parser::{Event, Parser};
+use yaml_rust::scanner::{TScalarStyle, TokenType};
 
 pub struct FromYAML;
 
+#[derive(Deserialize)]
+pub struct FromYAMLArgs {
+    #[serde(rename(deserialize = "merge-keys"))]
+    merge_keys: bool,
+    #[serde(rename(deserialize = "parse-dates"))]
+    parse_dates: bool,
+}
+
 impl WholeStreamCommand for FromYAML {
     fn name(&self) -> &str {
         "from-yaml"
@@ -11,6 +23,8 @@ impl WholeStreamCommand for FromYAML {
 
     fn signature(&self) -> Signature {
         Signature::build("from-yaml")
+            .switch("merge-keys")
+            .switch("parse-dates")
     }
 
     fn usage(&self) -> &str {
@@ -22,7 +36,7 @@ impl WholeStreamCommand for FromYAML {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        from_yaml(args, registry)
+        args.process(registry, from_yaml)?.run()
     }
 }
 
@@ -35,6 +49,8 @@ impl WholeStreamCommand for FromYML {
 
     fn signature(&self) -> Signature {
         Signature::build("from-yml")
+            .switch("merge-keys")
+            .switch("parse-dates")
     }
 
     fn usage(&self) -> &str {
@@ -46,11 +62,50 @@ impl WholeStreamCommand for FromYML {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        from_yaml(args, registry)
+        args.process(registry, from_yaml)?.run()
     }
 }
 
-fn convert_yaml_value_to_nu_value(v: &serde_yaml::Value, tag: impl Into<Tag>) -> Tagged<Value> {
+// Resolves the YAML `<<` merge key. Keys written in the mapping itself win over merged
+// ones, and earlier mappings in a `<<: [*a, *b]` list win over later ones.
+fn merge_mapping(mapping: &serde_yaml::Mapping) -> serde_yaml::Mapping {
+    let merge_key = serde_yaml::Value::String("<<".to_string());
+    let mut merged = serde_yaml::Mapping::new();
+
+    let sources: Vec<&serde_yaml::Mapping> = match mapping.get(&merge_key) {
+        Some(serde_yaml::Value::Mapping(source)) => vec![source],
+        Some(serde_yaml::Value::Sequence(sources)) => sources
+            .iter()
+            .filter_map(|source| match source {
+                serde_yaml::Value::Mapping(source) => Some(source),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+
+    for (k, v) in mapping.iter() {
+        if *k != merge_key {
+            merged.insert(k.clone(), v.clone());
+        }
+    }
+
+    for source in sources {
+        for (k, v) in merge_mapping(source).iter() {
+            if !merged.contains_key(k) {
+                merged.insert(k.clone(), v.clone());
+            }
+        }
+    }
+
+    merged
+}
+
+fn convert_yaml_value_to_nu_value(
+    v: &serde_yaml::Value,
+    merge_keys: bool,
+    tag: impl Into<Tag>,
+) -> Tagged<Value> {
     let tag = tag.into();
 
     match v {
@@ -62,19 +117,37 @@ fn convert_yaml_value_to_nu_value(v: &serde_yaml::Value, tag: impl Into<Tag>) ->
             Value::Primitive(Primitive::from(n.as_f64().unwrap())).tagged(tag)
         }
         serde_yaml::Value::String(s) => Value::string(s).tagged(tag),
+        // Each element gets its own origin, so it can be told apart from its siblings
         serde_yaml::Value::Sequence(a) => Value::List(
             a.iter()
-                .map(|x| convert_yaml_value_to_nu_value(x, tag))
+                .map(|x| {
+                    let element_tag = Tag {
+                        origin: Some(Uuid::new_v4()),
+                        span: tag.span,
+                    };
+                    convert_yaml_value_to_nu_value(x, merge_keys, element_tag)
+                })
                 .collect(),
         )
         .tagged(tag),
         serde_yaml::Value::Mapping(t) => {
             let mut collected = TaggedDictBuilder::new(tag);
 
+            let merged;
+            let t = if merge_keys {
+                merged = merge_mapping(t);
+                &merged
+            } else {
+                t
+            };
+
             for (k, v) in t.iter() {
                 match k {
                     serde_yaml::Value::String(k) => {
-                        collected.insert_tagged(k.clone(), convert_yaml_value_to_nu_value(v, tag));
+                        collected.insert_tagged(
+                            k.clone(),
+                            convert_yaml_value_to_nu_value(v, merge_keys, tag),
+                        );
                     }
                     _ => unimplemented!("Unknown key type"),
                 }
@@ -87,18 +160,239 @@ fn convert_yaml_value_to_nu_value(v: &serde_yaml::Value, tag: impl Into<Tag>) ->
     }
 }
 
+#[derive(Debug, Clone)]
+enum YamlPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+enum YamlFrame {
+    Mapping {
+        key: Option<String>,
+        awaiting_key: bool,
+        is_key: bool,
+    },
+    Sequence {
+        index: usize,
+        is_key: bool,
+    },
+}
+
+// Marks the node that just started as a key if its mapping is waiting for one
+fn begin_yaml_node(stack: &mut Vec<YamlFrame>) -> bool {
+    match stack.last_mut() {
+        Some(YamlFrame::Mapping {
+            key, awaiting_key, ..
+        }) if *awaiting_key => {
+            *key = None;
+            *awaiting_key = false;
+            true
+        }
+        _ => false,
+    }
+}
+
+fn end_yaml_value(stack: &mut Vec<YamlFrame>) {
+    match stack.last_mut() {
+        Some(YamlFrame::Mapping { awaiting_key, .. }) => *awaiting_key = true,
+        Some(YamlFrame::Sequence { index, .. }) => *index += 1,
+        None => {}
+    }
+}
+
+fn current_yaml_path(stack: &[YamlFrame]) -> Option<Vec<YamlPathSegment>> {
+    stack
+        .iter()
+        .map(|frame| match frame {
+            YamlFrame::Mapping { is_key: true, .. } | YamlFrame::Sequence { is_key: true, .. } => {
+                None
+            }
+            YamlFrame::Mapping { key, .. } => key.clone().map(YamlPathSegment::Key),
+            YamlFrame::Sequence { index, .. } => Some(YamlPathSegment::Index(*index)),
+        })
+        .collect()
+}
+
+// serde_yaml drops tags and quoting, so walk the raw events to find the scalars we want
+fn scalar_node_paths(
+    s: &str,
+    wanted: impl Fn(&str, &TScalarStyle, &Option<TokenType>) -> bool,
+) -> Vec<Vec<YamlPathSegment>> {
+    let mut parser = Parser::new(s.chars());
+    let mut stack: Vec<YamlFrame> = vec![];
+    let mut paths = vec![];
+
+    loop {
+        let event = match parser.next() {
+            Ok((Event::DocumentEnd, _)) | Ok((Event::StreamEnd, _)) | Err(_) => break,
+            Ok((event, _)) => event,
+        };
+
+        match event {
+            Event::Scalar(value, style, _, tag) => {
+                if let Some(YamlFrame::Mapping {
+                    key, awaiting_key, ..
+                }) = stack.last_mut()
+                {
+                    if *awaiting_key {
+                        *key = Some(value);
+                        *awaiting_key = false;
+                        continue;
+                    }
+                }
+
+                if wanted(value.as_str(), &style, &tag) {
+                    if let Some(path) = current_yaml_path(&stack) {
+                        paths.push(path);
+                    }
+                }
+
+                end_yaml_value(&mut stack);
+            }
+            Event::Alias(_) => {
+                if !begin_yaml_node(&mut stack) {
+                    end_yaml_value(&mut stack);
+                }
+            }
+            Event::MappingStart(_) => {
+                let is_key = begin_yaml_node(&mut stack);
+                stack.push(YamlFrame::Mapping {
+                    key: None,
+                    awaiting_key: true,
+                    is_key,
+                });
+            }
+            Event::SequenceStart(_) => {
+                let is_key = begin_yaml_node(&mut stack);
+                stack.push(YamlFrame::Sequence { index: 0, is_key });
+            }
+            Event::MappingEnd | Event::SequenceEnd => match stack.pop() {
+                Some(YamlFrame::Mapping { is_key: true, .. })
+                | Some(YamlFrame::Sequence { is_key: true, .. }) => {}
+                _ => end_yaml_value(&mut stack),
+            },
+            _ => {}
+        }
+    }
+
+    paths
+}
+
+fn is_tagged(tag: &Option<TokenType>, name: &str) -> bool {
+    match tag {
+        Some(TokenType::Tag(handle, suffix)) => handle == "!!" && suffix == name,
+        _ => false,
+    }
+}
+
+// A timestamp is one of the YAML 1.1 forms: 2024-01-02, 2024-01-02T03:04:05Z
+// with any offset, or a date and time without an offset, which is read as UTC
+fn parse_yaml_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+
+    if let Ok(d) = DateTime::parse_from_rfc3339(s) {
+        return Some(d.with_timezone(&Utc));
+    }
+
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_hms(0, 0, 0))
+        })
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+// Only unquoted scalars, or ones tagged !!timestamp, are taken as dates, so
+// "2024-01-02" in quotes stays text
+fn is_timestamp(value: &str, style: &TScalarStyle, tag: &Option<TokenType>) -> bool {
+    let plain = match style {
+        TScalarStyle::Plain => tag.is_none(),
+        _ => false,
+    };
+
+    (plain || is_tagged(tag, "timestamp")) && parse_yaml_timestamp(value).is_some()
+}
+
+fn decode_binary(value: &Value) -> Option<Value> {
+    match value {
+        Value::Primitive(Primitive::String(s)) => {
+            let encoded: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+            base64::decode(&encoded).ok().map(Value::Binary)
+        }
+        _ => None,
+    }
+}
+
+fn parse_date(value: &Value) -> Option<Value> {
+    match value {
+        Value::Primitive(Primitive::String(s)) => {
+            parse_yaml_timestamp(s).map(|d| Value::Primitive(Primitive::Date(d)))
+        }
+        _ => None,
+    }
+}
+
+fn convert_node_at(
+    value: &mut Tagged<Value>,
+    path: &[YamlPathSegment],
+    convert: fn(&Value) -> Option<Value>,
+) {
+    match path.split_first() {
+        None => {
+            if let Some(converted) = convert(&value.item) {
+                value.item = converted;
+            }
+        }
+        Some((YamlPathSegment::Key(key), rest)) => {
+            if let Value::Object(o) = &mut value.item {
+                if let Some(v) = o.entries.get_mut(key) {
+                    convert_node_at(v, rest, convert);
+                }
+            }
+        }
+        Some((YamlPathSegment::Index(idx), rest)) => {
+            if let Value::List(l) = &mut value.item {
+                if let Some(v) = l.get_mut(*idx) {
+                    convert_node_at(v, rest, convert);
+                }
+            }
+        }
+    }
+}
+
 pub fn from_yaml_string_to_value(
     s: String,
+    merge_keys: bool,
+    parse_dates: bool,
     tag: impl Into<Tag>,
 ) -> serde_yaml::Result<Tagged<Value>> {
     let v: serde_yaml::Value = serde_yaml::from_str(&s)?;
-    Ok(convert_yaml_value_to_nu_value(&v, tag))
+    let mut value = convert_yaml_value_to_nu_value(&v, merge_keys, tag);
+
+    for path in scalar_node_paths(&s, |_, _, tag| is_tagged(tag, "binary")) {
+        convert_node_at(&mut value, &path, decode_binary);
+    }
+
+    if parse_dates {
+        for path in scalar_node_paths(&s, is_timestamp) {
+            convert_node_at(&mut value, &path, parse_date);
+        }
+    }
+
+    Ok(value)
 }
 
-fn from_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let span = args.name_span();
-    let input = args.input;
+fn from_yaml(
+    FromYAMLArgs {
+        merge_keys,
+        parse_dates,
+    }: FromYAMLArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let span = name;
 
     let stream = async_stream_block! {
         let values: Vec<Tagged<Value>> = input.values.collect().await;
@@ -114,18 +408,16 @@ fn from_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStre
                     concat_string.push_str(&s);
                     concat_string.push_str("\n");
                 }
-                _ => yield Err(ShellError::labeled_error_with_secondary(
-                    "Expected a string from pipeline",
-                    "requires string input",
+                _ => yield Err(ShellError::type_mismatch(
+                    "string input",
+                    value.tagged_type_name(),
                     span,
-                    "value originates from here",
-                    value_tag.span,
                 )),
 
             }
         }
 
-        match from_yaml_string_to_value(concat_string, span) {
+        match from_yaml_string_to_value(concat_string.clone(), merge_keys, parse_dates, span) {
             Ok(x) => match x {
                 Tagged { item: Value::List(list), .. } => {
                     for l in list {
@@ -134,17 +426,60 @@ fn from_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStre
                 }
                 x => yield ReturnSuccess::value(x),
             },
-            Err(_) => if let Some(last_tag) = latest_tag {
-                yield Err(ShellError::labeled_error_with_secondary(
-                    "Could not parse as YAML",
-                    "input cannot be parsed as YAML",
-                    span,
-                    "value originates from here",
-                    last_tag.span,
-                ))
+            Err(e) => if let Some(last_tag) = latest_tag {
+                // Errors can only point into the command line, so spell out where the input went wrong
+                match e.location() {
+                    Some(location) => {
+                        // serde_yaml already counts both lines and columns from 1
+                        let (line, col) = (location.line(), location.column());
+                        let offending = concat_string
+                            .lines()
+                            .nth(line.saturating_sub(1))
+                            .unwrap_or("")
+                            .trim();
+
+                        let error = ShellError::labeled_error_with_secondary(
+                            format!("Could not parse as YAML at line {}, column {}", line, col),
+                            e.to_string(),
+                            span,
+                            format!("line {} is `{}`", line, offending),
+                            last_tag.span,
+                        );
+                        yield Err(error)
+                    }
+                    None => yield Err(ShellError::parse_failure("YAML", e, span, last_tag.span)),
+                }
             } ,
         }
     };
 
     Ok(stream.to_output_stream())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FromYAML;
+    use crate::commands::testing::run_command;
+    use crate::object::Value;
+    use crate::prelude::*;
+
+    #[test]
+    fn gives_list_elements_distinct_tags() {
+        let input = vec![Value::string("- 1\n- 2\n- [3, 4]").tagged_unknown()];
+
+        let returned = run_command(FromYAML, &[], input, &CommandRegistry::empty());
+
+        let tags: Vec<Tag> = returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(element)) => element.tag(),
+                other => panic!("expected an element, got {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(tags.len(), 3);
+        assert_ne!(tags[0], tags[1]);
+        assert_ne!(tags[1], tags[2]);
+        assert_ne!(tags[0], tags[2]);
+    }
+}
@@ -32,7 +32,57 @@ impl WholeStreamCommand for Get {
     }
 }
 
+// Binary data is addressed by position: a single index gives that byte as a
+// number, and a range such as 0..4 gives the bytes from 0 up to 4 as binary
+fn get_bytes(path: &Tagged<String>, bytes: &[u8], tag: Tag) -> Result<Tagged<Value>, ShellError> {
+    let position = |s: &str, default: usize| match s.trim() {
+        "" => Some(default),
+        s => s.parse::<usize>().ok(),
+    };
+
+    let out_of_range = || {
+        ShellError::labeled_error(
+            "Index out of range",
+            format!("there are only {} bytes", bytes.len()),
+            path.span(),
+        )
+    };
+
+    let not_a_position = || {
+        ShellError::labeled_error(
+            "Binary data can only be read by position",
+            "expected eg) 0 or 0..4",
+            path.span(),
+        )
+    };
+
+    match path.item.find("..") {
+        Some(split) => {
+            let start = position(&path.item[..split], 0).ok_or_else(not_a_position)?;
+            let end = position(&path.item[split + 2..], bytes.len()).ok_or_else(not_a_position)?;
+
+            if start > end || end > bytes.len() {
+                return Err(out_of_range());
+            }
+
+            Ok(Value::Binary(bytes[start..end].to_vec()).tagged(tag))
+        }
+        None => {
+            let index = position(&path.item, bytes.len()).ok_or_else(not_a_position)?;
+
+            match bytes.get(index) {
+                Some(byte) => Ok(Value::int(i64::from(*byte)).tagged(tag)),
+                None => Err(out_of_range()),
+            }
+        }
+    }
+}
+
 fn get_member(path: &Tagged<String>, obj: &Tagged<Value>) -> Result<Tagged<Value>, ShellError> {
+    if let Value::Binary(bytes) = &obj.item {
+        return get_bytes(path, bytes, obj.tag());
+    }
+
     let mut current = Some(obj);
     for p in path.split(".") {
         if let Some(obj) = current {
@@ -67,6 +117,8 @@ pub fn get(
     GetArgs { rest: fields }: GetArgs,
     RunnableContext { input, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
+    // Every row of the stream is looked up on its own, so a table gives back
+    // one value per row for each field asked for
     let stream = input
         .values
         .map(move |item| {
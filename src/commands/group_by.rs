@@ -0,0 +1,98 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::TaggedDictBuilder;
+use crate::prelude::*;
+use indexmap::IndexMap;
+
+pub struct GroupBy;
+
+#[derive(Deserialize)]
+pub struct GroupByArgs {
+    key: Tagged<Value>,
+}
+
+impl WholeStreamCommand for GroupBy {
+    fn name(&self) -> &str {
+        "group-by"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("group-by").required("key", SyntaxType::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Group the rows by the value of a column, or by the result of a block, into a single row of lists."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, group_by)?.run()
+    }
+}
+
+// A block is run on each row and its result becomes the key. Anything else
+// names the column to read the key from.
+fn group_key(key: &Tagged<Value>, row: &Tagged<Value>) -> Result<String, ShellError> {
+    let value = match &key.item {
+        Value::Block(block) => block.invoke(row)?.item,
+        column => {
+            let column = column.as_string()?;
+            match row.get_data_by_path(row.tag(), &column) {
+                Some(value) => value.item.clone(),
+                None => {
+                    return Err(ShellError::labeled_error_with_secondary(
+                        "Could not find column to group by",
+                        format!("missing '{}'", column),
+                        key.span(),
+                        "row originates from here",
+                        row.span(),
+                    ))
+                }
+            }
+        }
+    };
+
+    value.as_string().map_err(|_| {
+        ShellError::labeled_error_with_secondary(
+            "Could not use the value as a group",
+            format!("{} can not be used as a key", value.type_name()),
+            key.span(),
+            "row originates from here",
+            row.span(),
+        )
+    })
+}
+
+pub fn group_by(
+    GroupByArgs { key }: GroupByArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        // Groups keep the order their first row arrived in
+        let mut groups: IndexMap<String, Vec<Tagged<Value>>> = IndexMap::new();
+
+        for row in values {
+            match group_key(&key, &row) {
+                Ok(group) => groups.entry(group).or_insert_with(Vec::new).push(row),
+                Err(e) => {
+                    yield Err(e);
+                    return;
+                }
+            }
+        }
+
+        let mut grouped = TaggedDictBuilder::new(name);
+        for (group, rows) in groups {
+            grouped.insert(group, Value::List(rows));
+        }
+
+        yield ReturnSuccess::value(grouped.into_tagged_value());
+    };
+
+    Ok(stream.to_output_stream())
+}
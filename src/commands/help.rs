@@ -1,6 +1,6 @@
-use crate::commands::command::CommandAction;
 use crate::commands::PerItemCommand;
 use crate::errors::ShellError;
+use crate::object::{command_dict, TaggedDictBuilder};
 use crate::parser::registry;
 use crate::prelude::*;
 
@@ -12,7 +12,7 @@ impl PerItemCommand for Help {
     }
 
     fn signature(&self) -> registry::Signature {
-        Signature::build("help").rest(SyntaxType::Any)
+        Signature::build("help").optional("command", SyntaxType::Any)
     }
 
     fn usage(&self) -> &str {
@@ -22,34 +22,48 @@ impl PerItemCommand for Help {
     fn run(
         &self,
         call_info: &CallInfo,
-        _registry: &CommandRegistry,
+        registry: &CommandRegistry,
         _raw_args: &RawCommandArgs,
         _input: Tagged<Value>,
     ) -> Result<OutputStream, ShellError> {
         let span = call_info.name_span;
 
-        if call_info.args.len() == 0 {
-            return Ok(
-                vec![
-                Ok(ReturnSuccess::Action(
-                    CommandAction::EnterHelpShell(
-                        Tagged::from_simple_spanned_item(Value::nothing(), span)
-                    )))].into()
-            )
-        }
+        match call_info.args.nth(0) {
+            None => {
+                let mut names = registry.names();
+                names.sort();
+
+                let mut commands = VecDeque::new();
+
+                for name in names {
+                    if let Some(command) = registry.get_command(&name) {
+                        let mut spec = TaggedDictBuilder::new(Tag::unknown_origin(span));
+                        spec.insert("name", Value::string(name));
+                        spec.insert("description", Value::string(command.usage()));
+                        commands.push_back(ReturnSuccess::value(spec.into_tagged_value()));
+                    }
+                }
 
-        match call_info.args.expect_nth(0)? {
-            Tagged {
-                item: Value::Primitive(Primitive::String(document)),
-                ..
-            } => Ok(vec![Ok(ReturnSuccess::Action(CommandAction::EnterHelpShell(
-                Tagged::from_simple_spanned_item(Value::string(document), span)
-            )))]
-            .into()),
-            x => Ok(
-                vec![Ok(ReturnSuccess::Action(CommandAction::EnterHelpShell(x.clone())))]
-                .into(),
-            ),
+                Ok(commands.to_output_stream())
+            }
+            Some(Tagged {
+                item: Value::Primitive(Primitive::String(name)),
+                tag,
+            }) => match registry.get_command(name) {
+                Some(command) => {
+                    Ok(vec![ReturnSuccess::value(command_dict(command, span))].into())
+                }
+                None => Err(ShellError::labeled_error(
+                    format!("Unknown command '{}'", name),
+                    "not a registered command",
+                    tag.span,
+                )),
+            },
+            Some(other) => Err(ShellError::labeled_error(
+                "Expected a command name",
+                "requires a command name",
+                other.span(),
+            )),
         }
     }
 }
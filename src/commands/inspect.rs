@@ -0,0 +1,40 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Inspect;
+
+impl WholeStreamCommand for Inspect {
+    fn name(&self) -> &str {
+        "inspect"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("inspect")
+    }
+
+    fn usage(&self) -> &str {
+        "Print each value to stderr as it passes through, leaving the pipeline unchanged."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        inspect(args, registry)
+    }
+}
+
+pub fn inspect(args: CommandArgs, _registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let host = args.host.clone();
+    let input = args.input;
+
+    Ok(input
+        .values
+        .map(move |v| {
+            host.lock().unwrap().stderr(&format!("{:?}", v));
+            ReturnSuccess::value(v)
+        })
+        .to_output_stream())
+}
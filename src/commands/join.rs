@@ -0,0 +1,166 @@
+use crate::commands::zip::merge_rows;
+use crate::commands::{RawCommandArgs, UnevaluatedCallInfo, WholeStreamCommand};
+use crate::errors::ShellError;
+use crate::object::TaggedDictBuilder;
+use crate::parser::hir::{self, Expression};
+use crate::prelude::*;
+use std::path::PathBuf;
+
+pub struct Join;
+
+#[derive(Deserialize)]
+pub struct JoinArgs {
+    key: Tagged<String>,
+    other: Tagged<PathBuf>,
+    left: bool,
+    inner: bool,
+}
+
+impl WholeStreamCommand for Join {
+    fn name(&self) -> &str {
+        "join"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("join")
+            .required("key", SyntaxType::Member)
+            .required("other", SyntaxType::Path)
+            .switch("left")
+            .switch("inner")
+    }
+
+    fn usage(&self) -> &str {
+        "Join each row with the rows of another table file that share the key column, keeping unmatched rows with '--left'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        Ok(args.process_raw(registry, join)?.run())
+    }
+}
+
+// Rows without the key column never match anything
+fn key_of(row: &Tagged<Value>, key: &str) -> Option<Value> {
+    row.get_data_by_path(row.tag(), key)
+        .map(|value| value.item.clone())
+}
+
+// An unmatched row keeps its own columns, and every column only the other
+// table has is added as nothing
+fn pad_row(
+    row: Tagged<Value>,
+    columns: &[String],
+    name: Span,
+) -> Result<Tagged<Value>, ShellError> {
+    match &row.item {
+        Value::Object(o) => {
+            let mut padded = TaggedDictBuilder::new(row.tag());
+
+            for (k, v) in o.entries.iter() {
+                padded.insert_tagged(k.clone(), v.clone());
+            }
+
+            for column in columns.iter().filter(|c| !o.entries.contains_key(*c)) {
+                padded.insert(column.clone(), Value::nothing());
+            }
+
+            Ok(padded.into_tagged_value())
+        }
+        _ => Err(ShellError::type_mismatch(
+            "a row to join",
+            row.tagged_type_name(),
+            name,
+        )),
+    }
+}
+
+fn join(
+    JoinArgs {
+        key,
+        other,
+        left,
+        inner,
+    }: JoinArgs,
+    context: RunnableContext,
+    raw_args: RawCommandArgs,
+) -> Result<OutputStream, ShellError> {
+    if left && inner {
+        return Err(ShellError::labeled_error(
+            "Can only join one way",
+            "use either --left or --inner",
+            context.name,
+        ));
+    }
+
+    // The other table is read the same way `open` would read it, so any
+    // format with a from- command can be joined
+    let open = context.expect_command("open");
+    let path = Expression::file_path(other.item.clone(), other.span());
+    let open_args = RawCommandArgs {
+        host: raw_args.host,
+        shell_manager: raw_args.shell_manager,
+        call_info: UnevaluatedCallInfo {
+            args: hir::Call::new(
+                Box::new(Expression::synthetic_string("open")),
+                Some(vec![path]),
+                None,
+            ),
+            source: raw_args.call_info.source,
+            source_map: raw_args.call_info.source_map,
+            name_span: raw_args.call_info.name_span,
+        },
+    };
+
+    let stream = async_stream_block! {
+        let mut opened = open.run(open_args.with_input(vec![]), &context.commands);
+        let mut others: Vec<Tagged<Value>> = vec![];
+
+        for returned in opened.drain_vec().await {
+            match returned {
+                Ok(ReturnSuccess::Value(row)) => others.push(row),
+                Ok(_) => {}
+                Err(e) => {
+                    yield Err(e);
+                    return;
+                }
+            }
+        }
+
+        let mut other_columns: Vec<String> = vec![];
+        for row in others.iter() {
+            for column in row.data_descriptors() {
+                if !other_columns.contains(&column) {
+                    other_columns.push(column);
+                }
+            }
+        }
+
+        let values: Vec<Tagged<Value>> = context.input.values.collect().await;
+
+        for row in values {
+            let matches: Vec<&Tagged<Value>> = match key_of(&row, &key.item) {
+                Some(value) => others
+                    .iter()
+                    .filter(|other| key_of(other, &key.item).as_ref() == Some(&value))
+                    .collect(),
+                None => vec![],
+            };
+
+            if matches.is_empty() {
+                if left {
+                    yield pad_row(row, &other_columns, context.name).map(ReturnSuccess::Value);
+                }
+                continue;
+            }
+
+            for other in matches {
+                yield merge_rows(row.clone(), other.clone(), context.name).map(ReturnSuccess::Value);
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -1,3 +1,4 @@
+use crate::commands::first::Amount;
 use crate::commands::WholeStreamCommand;
 use crate::errors::ShellError;
 use crate::parser::CommandRegistry;
@@ -12,11 +13,11 @@ impl WholeStreamCommand for Last {
 
     fn signature(&self) -> Signature {
         Signature::build("last")
-            .required("amount", SyntaxType::Number)
+            .required("amount", SyntaxType::Literal)
     }
 
     fn usage(&self) -> &str {
-        "Show only the last number of rows."
+        "Show only the last number of rows, or the last share of them given as a percentage like '10%'."
     }
 
     fn run(
@@ -31,30 +32,21 @@ impl WholeStreamCommand for Last {
 fn last(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
     let args = args.evaluate_once(registry)?;
 
-    let amount = args.expect_nth(0)?.as_i64();
+    let amount = Amount::from_value(args.expect_nth(0)?)?;
 
-    let amount = match amount {
-        Ok(o) => o,
-        Err(_) => {
+    if let Amount::Rows(rows) = amount {
+        if rows <= 0 {
             return Err(ShellError::labeled_error(
-                "Value is not a number",
-                "expected integer",
+                "Value is too low",
+                "expected a positive integer",
                 args.expect_nth(0)?.span(),
-            ))
+            ));
         }
-    };
-
-    if amount <= 0 {
-        return Err(ShellError::labeled_error(
-            "Value is too low",
-            "expected a positive integer",
-            args.expect_nth(0)?.span(),
-        ));
     }
 
     let stream = async_stream_block! {
         let v: Vec<_> = args.input.into_vec().await;
-        let k = v.len() - (amount as usize);
+        let k = v.len().saturating_sub(amount.of(v.len()));
         for x in v[k..].iter() {
             let y: Tagged<Value> = x.clone();
             yield ReturnSuccess::value(y)
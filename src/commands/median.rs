@@ -0,0 +1,125 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{Primitive, Value};
+use crate::prelude::*;
+
+pub struct Median;
+
+#[derive(Deserialize)]
+pub struct MedianArgs {
+    column: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Median {
+    fn name(&self) -> &str {
+        "median"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("median").optional("column", SyntaxType::Member)
+    }
+
+    fn usage(&self) -> &str {
+        "Find the middle value of a column or a stream of numbers."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, median)?.run()
+    }
+}
+
+// Empty cells are left out, so a partly filled column still has a median and a mode
+pub(crate) fn column_values(
+    rows: Vec<Tagged<Value>>,
+    column: &Option<Tagged<String>>,
+) -> Result<Vec<Tagged<Value>>, ShellError> {
+    let values = match column {
+        None => rows,
+        Some(column) => rows
+            .iter()
+            .map(|row| match row.get_data_by_path(row.tag(), &column.item) {
+                Some(value) => Ok(value.map(|value| value.clone())),
+                None => Err(ShellError::labeled_error_with_secondary(
+                    "Could not find column",
+                    format!("missing '{}'", column.item),
+                    column.span(),
+                    "row originates from here",
+                    row.span(),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    Ok(values
+        .into_iter()
+        .filter(|value| match value.item {
+            Value::Primitive(Primitive::Nothing) => false,
+            _ => true,
+        })
+        .collect())
+}
+
+fn median(
+    MedianArgs { column }: MedianArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let rows: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let values = match column_values(rows, &column) {
+            Ok(values) => values,
+            Err(err) => {
+                yield Err(err);
+                return;
+            }
+        };
+
+        let mut numbers = vec![];
+
+        for value in values {
+            let number = match &value.item {
+                Value::Primitive(Primitive::Int(i)) => Decimal::from(*i),
+                Value::Primitive(Primitive::Decimal(d)) => *d,
+                other => {
+                    let error = ShellError::labeled_error_with_secondary(
+                        "Can only find the median of numbers",
+                        format!("found {}", other.type_name()),
+                        name,
+                        "value originates from here",
+                        value.span(),
+                    );
+                    yield Err(error);
+                    return;
+                }
+            };
+
+            numbers.push((number, value));
+        }
+
+        numbers.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let middle = numbers.len() / 2;
+
+        // An odd count has a value in the middle, an even count sits between two
+        let median = match numbers.len() {
+            0 => {
+                yield Err(ShellError::labeled_error(
+                    "Could not find a median",
+                    "no numbers to take the median of",
+                    name,
+                ));
+                return;
+            }
+            count if count % 2 == 1 => numbers[middle].1.item.clone(),
+            _ => Value::decimal((numbers[middle - 1].0 + numbers[middle].0) / Decimal::from(2)),
+        };
+
+        yield ReturnSuccess::value(median.simple_spanned(name));
+    };
+
+    Ok(stream.to_output_stream())
+}
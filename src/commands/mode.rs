@@ -0,0 +1,70 @@
+use crate::commands::duplicates::ValueCounts;
+use crate::commands::median::column_values;
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Mode;
+
+#[derive(Deserialize)]
+pub struct ModeArgs {
+    column: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Mode {
+    fn name(&self) -> &str {
+        "mode"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("mode").optional("column", SyntaxType::Member)
+    }
+
+    fn usage(&self) -> &str {
+        "Find the most frequent values of a column or a stream."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, mode)?.run()
+    }
+}
+
+fn mode(
+    ModeArgs { column }: ModeArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let rows: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let values = match column_values(rows, &column) {
+            Ok(values) => values,
+            Err(err) => {
+                yield Err(err);
+                return;
+            }
+        };
+
+        let mut counts = ValueCounts::default();
+
+        for value in values {
+            counts.add(value);
+        }
+
+        let counts = counts.into_counts();
+
+        let most = counts.iter().map(|(_, occurrences)| *occurrences).max();
+
+        // Ties are all shown, in the order they first appeared
+        for (value, occurrences) in counts {
+            if Some(occurrences) == most {
+                yield ReturnSuccess::value(value);
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
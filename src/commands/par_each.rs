@@ -0,0 +1,128 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use futures::channel::oneshot;
+use std::sync::mpsc;
+
+pub struct ParEach;
+
+#[derive(Deserialize)]
+pub struct ParEachArgs {
+    block: value::Block,
+    threads: Option<Tagged<i64>>,
+    unordered: bool,
+}
+
+impl WholeStreamCommand for ParEach {
+    fn name(&self) -> &str {
+        "par-each"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("par-each")
+            .required("block", SyntaxType::Block)
+            .named("threads", SyntaxType::Number)
+            .switch("unordered")
+    }
+
+    fn usage(&self) -> &str {
+        "Run a block on each row in parallel, keeping the row order unless '--unordered' is given. The block is evaluated as an expression, like `{ $it.name }`, and can't run commands."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, par_each)?.run()
+    }
+}
+
+fn par_each(
+    ParEachArgs {
+        block,
+        threads,
+        unordered,
+    }: ParEachArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let threads = match threads {
+        Some(Tagged { item, tag }) if item < 1 => {
+            return Err(ShellError::labeled_error(
+                "Expected at least one thread",
+                "must be 1 or more",
+                tag.span,
+            ))
+        }
+        Some(Tagged { item, .. }) => item as usize,
+        None => num_cpus::get(),
+    };
+
+    // Blocks are evaluated the way `where` evaluates them: each expression
+    // in the block runs against the row and the last one is the result.
+    // There is no command registry on the worker threads, so a block that
+    // holds a pipeline such as `{ open $it.name | count }` can't run here.
+    let jobs = pool(block, threads);
+
+    // Rows wait in the pool's queue until a worker is free, and the stream only
+    // asks for `threads` rows ahead, so the queue never holds more than that
+    let runs = input.values.map(move |row| {
+        let jobs = jobs.clone();
+
+        async move {
+            let (sender, receiver) = oneshot::channel();
+            let _ = jobs.send((row, sender));
+
+            match receiver.await {
+                Ok(Ok(value)) => ReturnSuccess::value(value),
+                Ok(Err(e)) => Err(e),
+                Err(_) => Err(ShellError::labeled_error(
+                    "par-each block stopped unexpectedly",
+                    "block stopped before finishing",
+                    name,
+                )),
+            }
+        }
+    });
+
+    let results = if unordered {
+        runs.buffer_unordered(threads).boxed()
+    } else {
+        runs.buffered(threads).boxed()
+    };
+
+    Ok(results.to_output_stream())
+}
+
+type Job = (
+    Tagged<Value>,
+    oneshot::Sender<Result<Tagged<Value>, ShellError>>,
+);
+
+/// Start `threads` workers that run `block` on the rows sent to them. The
+/// workers stop once every sender for the queue has been dropped.
+fn pool(block: value::Block, threads: usize) -> mpsc::Sender<Job> {
+    let (jobs, queue) = mpsc::channel::<Job>();
+    let queue = Arc::new(Mutex::new(queue));
+
+    for _ in 0..threads {
+        let block = block.clone();
+        let queue = queue.clone();
+
+        std::thread::spawn(move || loop {
+            let job = match queue.lock() {
+                Ok(queue) => queue.recv(),
+                Err(_) => return,
+            };
+
+            match job {
+                Ok((row, sender)) => {
+                    let _ = sender.send(block.invoke(&row));
+                }
+                Err(_) => return,
+            }
+        });
+    }
+
+    jobs
+}
@@ -5,7 +5,7 @@ use crate::object::base::select_fields;
 use crate::prelude::*;
 
 #[derive(Deserialize)]
-struct PickArgs {
+pub(crate) struct PickArgs {
     rest: Vec<Tagged<String>>,
 }
 
@@ -33,7 +33,7 @@ impl WholeStreamCommand for Pick {
     }
 }
 
-fn pick(
+pub(crate) fn pick(
     PickArgs { rest: fields }: PickArgs,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
@@ -1,4 +1,4 @@
-use crate::commands::UnevaluatedCallInfo;
+use crate::commands::{RawCommandArgs, UnevaluatedCallInfo, WholeStreamCommand};
 use crate::context::SpanSource;
 use crate::errors::ShellError;
 use crate::object::Value;
@@ -6,14 +6,30 @@ use crate::parser::hir::SyntaxType;
 use crate::parser::registry::Signature;
 use crate::prelude::*;
 use base64::encode;
+use futures::future::BoxFuture;
 use mime::Mime;
 use std::path::PathBuf;
 use std::str::FromStr;
+use surf::http::header::{HeaderName, HeaderValue};
+use surf::middleware::{HttpClient, Middleware, Next, Request, Response};
 use surf::mime;
 use uuid::Uuid;
+
 pub struct Post;
 
-impl PerItemCommand for Post {
+#[derive(Deserialize)]
+pub struct PostArgs {
+    path: Tagged<String>,
+    body: Option<Tagged<Value>>,
+    user: Option<String>,
+    password: Option<String>,
+    #[serde(rename(deserialize = "content-type"))]
+    content_type: Option<Tagged<String>>,
+    header: Option<Tagged<String>>,
+    raw: bool,
+}
+
+impl WholeStreamCommand for Post {
     fn name(&self) -> &str {
         "post"
     }
@@ -21,9 +37,11 @@ impl PerItemCommand for Post {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .required("path", SyntaxType::Any)
-            .required("body", SyntaxType::Any)
+            .optional("body", SyntaxType::Any)
             .named("user", SyntaxType::Any)
             .named("password", SyntaxType::Any)
+            .named("content-type", SyntaxType::Any)
+            .named("header", SyntaxType::Any)
             .switch("raw")
     }
 
@@ -33,50 +51,110 @@ impl PerItemCommand for Post {
 
     fn run(
         &self,
-        call_info: &CallInfo,
+        args: CommandArgs,
         registry: &CommandRegistry,
-        raw_args: &RawCommandArgs,
-        _input: Tagged<Value>,
     ) -> Result<OutputStream, ShellError> {
-        run(call_info, registry, raw_args)
+        Ok(args.process_raw(registry, run)?.run())
+    }
+}
+
+// Without a body argument the pipeline is posted: text is joined line by line,
+// a single binary value is sent as is and anything else goes out as JSON
+fn body_from_input(values: Vec<Tagged<Value>>, span: Span) -> Tagged<Value> {
+    let all_text = values.iter().all(|v| match v.item {
+        Value::Primitive(Primitive::String(_)) => true,
+        _ => false,
+    });
+
+    match values.len() {
+        1 => values.into_iter().next().unwrap(),
+        _ if all_text => Value::string(
+            values
+                .iter()
+                .filter_map(|v| v.as_string().ok())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .tagged(span),
+        _ => Value::List(values).tagged(span),
     }
 }
 
 fn run(
-    call_info: &CallInfo,
-    registry: &CommandRegistry,
-    raw_args: &RawCommandArgs,
+    PostArgs {
+        path,
+        body,
+        user,
+        password,
+        content_type,
+        header,
+        raw: has_raw,
+    }: PostArgs,
+    RunnableContext {
+        input,
+        commands: registry,
+        ..
+    }: RunnableContext,
+    raw_args: RawCommandArgs,
 ) -> Result<OutputStream, ShellError> {
-    let call_info = call_info.clone();
-    let path = match call_info
-        .args
-        .nth(0)
-        .ok_or_else(|| ShellError::string(&format!("No url specified")))?
-    {
-        file => file.clone(),
+    let path_str = path.item.clone();
+    let path_span = path.span();
+
+    let content_type = match content_type {
+        Some(Tagged { item, tag }) => match Mime::from_str(&item) {
+            Ok(mime) => Some(mime),
+            Err(_) => {
+                return Err(ShellError::labeled_error(
+                    "Invalid content type",
+                    "expected a content type such as application/json",
+                    tag.span,
+                ))
+            }
+        },
+        None => None,
     };
-    let body = match call_info
-        .args
-        .nth(1)
-        .ok_or_else(|| ShellError::string(&format!("No body specified")))?
-    {
-        file => file.clone(),
+
+    let header = match header {
+        Some(Tagged { item, tag }) => {
+            let mut parts = item.splitn(2, ':');
+            let name = parts.next().map(|name| name.trim()).unwrap_or("");
+            let value = parts.next().map(|value| value.trim().to_string());
+
+            let value = value.map(|value| HeaderValue::from_str(&value));
+
+            match (HeaderName::from_bytes(name.as_bytes()), value) {
+                (Ok(name), Some(Ok(value))) => Some(ExtraHeader(name, value)),
+                _ => {
+                    return Err(ShellError::labeled_error(
+                        "Invalid header",
+                        "expected a header such as 'Accept: text/plain'",
+                        tag.span,
+                    ))
+                }
+            }
+        }
+        None => None,
     };
-    let path_str = path.as_string()?;
-    //let body_str = body.as_string()?;
-    let path_span = path.span();
-    let has_raw = call_info.args.has("raw");
-    let user = call_info.args.get("user").map(|x| x.as_string().unwrap());
-    let password = call_info
-        .args
-        .get("password")
-        .map(|x| x.as_string().unwrap());
-    let registry = registry.clone();
-    let raw_args = raw_args.clone();
 
     let stream = async_stream_block! {
+        let body = match body {
+            Some(body) => body,
+            None => {
+                let values: Vec<Tagged<Value>> = input.values.collect().await;
+                body_from_input(values, path_span)
+            }
+        };
+
+        let options = PostOptions { user, password, content_type, header };
+
         let (file_extension, contents, contents_tag, span_source) =
-            post(&path_str, &body, user, password, path_span, &registry, &raw_args).await.unwrap();
+            match post(&path_str, &body, options, path_span, &registry, &raw_args).await {
+                Ok(response) => response,
+                Err(e) => {
+                    yield Err(e);
+                    return;
+                }
+            };
 
         let file_extension = if has_raw {
             None
@@ -139,11 +217,38 @@ fn run(
     Ok(stream.to_output_stream())
 }
 
+pub struct PostOptions {
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub content_type: Option<Mime>,
+    pub header: Option<ExtraHeader>,
+}
+
+// surf only sets headers whose names are known when nu is built, so one given
+// on the command line is added as the request goes out
+pub struct ExtraHeader(HeaderName, HeaderValue);
+
+impl<C: HttpClient> Middleware<C> for ExtraHeader {
+    fn handle<'a>(
+        &'a self,
+        mut req: Request,
+        client: C,
+        next: Next<'a, C>,
+    ) -> BoxFuture<'a, Result<Response, surf::Exception>> {
+        req.headers_mut().insert(self.0.clone(), self.1.clone());
+        next.run(req, client)
+    }
+}
+
+enum PostBody {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
 pub async fn post(
     location: &str,
     body: &Tagged<Value>,
-    user: Option<String>,
-    password: Option<String>,
+    options: PostOptions,
     span: Span,
     registry: &CommandRegistry,
     raw_args: &RawCommandArgs,
@@ -151,32 +256,20 @@ pub async fn post(
     let registry = registry.clone();
     let raw_args = raw_args.clone();
     if location.starts_with("http:") || location.starts_with("https:") {
-        let login = match (user, password) {
+        let login = match (options.user, options.password) {
             (Some(user), Some(password)) => Some(encode(&format!("{}:{}", user, password))),
             (Some(user), _) => Some(encode(&format!("{}:", user))),
             _ => None,
         };
-        let response = match body {
+        let body = match body {
             Tagged {
                 item: Value::Primitive(Primitive::String(body_str)),
                 ..
-            } => {
-                let mut s = surf::post(location).body_string(body_str.to_string());
-                if let Some(login) = login {
-                    s = s.set_header("Authorization", format!("Basic {}", login));
-                }
-                s.await
-            }
+            } => PostBody::Text(body_str.to_string()),
             Tagged {
                 item: Value::Binary(b),
                 ..
-            } => {
-                let mut s = surf::post(location).body_bytes(b);
-                if let Some(login) = login {
-                    s = s.set_header("Authorization", format!("Basic {}", login));
-                }
-                s.await
-            }
+            } => PostBody::Bytes(b.clone()),
             Tagged { item, tag } => {
                 if let Some(converter) = registry.get_command("to-json") {
                     let new_args = RawCommandArgs {
@@ -218,12 +311,7 @@ pub async fn post(
                         }
                     }
 
-                    let mut s = surf::post(location).body_string(result_string);
-
-                    if let Some(login) = login {
-                        s = s.set_header("Authorization", format!("Basic {}", login));
-                    }
-                    s.await
+                    PostBody::Text(result_string)
                 } else {
                     return Err(ShellError::labeled_error(
                         "Could not automatically convert table",
@@ -233,7 +321,40 @@ pub async fn post(
                 }
             }
         };
+
+        let mut s = match body {
+            PostBody::Text(text) => surf::post(location).body_string(text),
+            PostBody::Bytes(bytes) => surf::post(location).body_bytes(bytes),
+        };
+
+        // Set after the body, which otherwise picks its own content type
+        if let Some(content_type) = options.content_type {
+            s = s.set_mime(content_type);
+        }
+        if let Some(login) = login {
+            s = s.set_header("Authorization", format!("Basic {}", login));
+        }
+        if let Some(header) = options.header {
+            s = s.middleware(header);
+        }
+
+        let mut response = s.await;
+
         match response {
+            Ok(ref mut r) if !r.status().is_success() => {
+                let status = r.status();
+                let body = r.body_string().await.unwrap_or_default();
+                let snippet: String = body.chars().take(100).collect();
+
+                Err(ShellError::labeled_error(
+                    format!("Post failed with status {}", status),
+                    match snippet.trim() {
+                        "" => "no response body".to_string(),
+                        snippet => snippet.to_string(),
+                    },
+                    span,
+                ))
+            }
             Ok(mut r) => match r.headers().get("content-type") {
                 Some(content_type) => {
                     let content_type = Mime::from_str(content_type).unwrap();
@@ -0,0 +1,444 @@
+use crate::errors::ShellError;
+use crate::parser::{Operator, Unit};
+use crate::prelude::*;
+use regex::Regex;
+use std::str::FromStr;
+
+const COMPARISON_OPERATORS: [&str; 8] = ["==", "!=", "<=", ">=", "=~", "!~", "<", ">"];
+
+const TYPE_NAMES: [&str; 8] = [
+    "string", "int", "decimal", "bool", "date", "list", "object", "nothing",
+];
+
+#[derive(Debug, Clone)]
+enum PredicateToken {
+    Word(String),
+    Literal(Value),
+    Operator(&'static str),
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+enum Test {
+    Compare(Operator, Value),
+    Matches(Regex, bool),
+    Contains(Value),
+    Is(&'static str),
+}
+
+#[derive(Debug, Clone)]
+enum PredicateExpression {
+    Column(String, Test),
+    And(Box<PredicateExpression>, Box<PredicateExpression>),
+    Or(Box<PredicateExpression>, Box<PredicateExpression>),
+    Not(Box<PredicateExpression>),
+}
+
+fn is_keyword(word: &str) -> bool {
+    word == "and" || word == "or" || word == "not" || word == "contains" || word == "is"
+}
+
+fn is_type(value: &Value, type_name: &str) -> bool {
+    match (type_name, value) {
+        ("string", Value::Primitive(Primitive::String(_))) => true,
+        ("int", Value::Primitive(Primitive::Int(_)))
+        | ("int", Value::Primitive(Primitive::BigInt(_))) => true,
+        ("decimal", Value::Primitive(Primitive::Decimal(_))) => true,
+        ("bool", Value::Primitive(Primitive::Boolean(_))) => true,
+        ("date", Value::Primitive(Primitive::Date(_))) => true,
+        ("list", Value::List(_)) => true,
+        ("object", Value::Object(_)) => true,
+        ("nothing", Value::Primitive(Primitive::Nothing)) => true,
+        _ => false,
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+// Numbers may carry a size unit, eg) 10, 1.5 or 1mb
+fn number_literal(word: &str) -> Option<Value> {
+    let (negative, word) = if word.starts_with('-') {
+        (true, &word[1..])
+    } else {
+        (false, word)
+    };
+
+    let split = word
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or_else(|| word.len());
+    let (number, unit) = word.split_at(split);
+
+    let number = match (i64::from_str(number), Decimal::from_str(number)) {
+        (Ok(int), _) if negative => Number::Int(-int),
+        (Ok(int), _) => Number::Int(int),
+        (_, Ok(decimal)) if negative => Number::Decimal(-decimal),
+        (_, Ok(decimal)) => Number::Decimal(decimal),
+        _ => return None,
+    };
+
+    match unit {
+        "" => Some(Value::number(number)),
+        unit => Unit::from_str(unit).ok().map(|unit| unit.compute(number)),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<PredicateToken>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = vec![];
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        let rest: String = chars[pos..].iter().take(2).collect();
+
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c == '(' {
+            tokens.push(PredicateToken::Open);
+            pos += 1;
+        } else if c == ')' {
+            tokens.push(PredicateToken::Close);
+            pos += 1;
+        } else if c == '"' || c == '\'' {
+            let len = match chars[pos + 1..].iter().position(|x| *x == c) {
+                Some(len) => len,
+                None => return Err("this string is never closed".to_string()),
+            };
+            let string: String = chars[pos + 1..pos + 1 + len].iter().collect();
+            tokens.push(PredicateToken::Literal(Value::string(string)));
+            pos += len + 2;
+        } else if let Some(op) = COMPARISON_OPERATORS
+            .iter()
+            .find(|op| rest.starts_with(**op))
+        {
+            tokens.push(PredicateToken::Operator(*op));
+            pos += op.len();
+        } else if is_word_char(c) {
+            let word: String = chars[pos..]
+                .iter()
+                .take_while(|c| is_word_char(**c))
+                .collect();
+            pos += word.chars().count();
+
+            let starts_numeric = word
+                .trim_start_matches('-')
+                .starts_with(|c: char| c.is_ascii_digit());
+
+            if starts_numeric {
+                match number_literal(&word) {
+                    Some(number) => tokens.push(PredicateToken::Literal(number)),
+                    None => return Err(format!("'{}' is not a number", word)),
+                }
+            } else {
+                tokens.push(PredicateToken::Word(word));
+            }
+        } else {
+            return Err(format!("unexpected '{}'", c));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct PredicateParser {
+    tokens: Vec<PredicateToken>,
+    pos: usize,
+}
+
+impl PredicateParser {
+    fn next(&mut self) -> Option<PredicateToken> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.tokens.get(self.pos) {
+            Some(PredicateToken::Word(word)) if word == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn or(&mut self) -> Result<PredicateExpression, String> {
+        let mut left = self.and()?;
+
+        while self.eat_keyword("or") {
+            let right = self.and()?;
+            left = PredicateExpression::Or(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<PredicateExpression, String> {
+        let mut left = self.unary()?;
+
+        while self.eat_keyword("and") {
+            let right = self.unary()?;
+            left = PredicateExpression::And(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<PredicateExpression, String> {
+        if self.eat_keyword("not") {
+            return Ok(PredicateExpression::Not(Box::new(self.unary()?)));
+        }
+
+        if let Some(PredicateToken::Open) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            let inner = self.or()?;
+
+            return match self.next() {
+                Some(PredicateToken::Close) => Ok(inner),
+                _ => Err("expected ')'".to_string()),
+            };
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<PredicateExpression, String> {
+        let column = match self.next() {
+            Some(PredicateToken::Word(ref word)) if !is_keyword(word) => word.clone(),
+            _ => return Err("expected a column name".to_string()),
+        };
+
+        let op = match self.next() {
+            Some(PredicateToken::Operator(op)) => op,
+            Some(PredicateToken::Word(ref word)) if word == "contains" => "contains",
+            Some(PredicateToken::Word(ref word)) if word == "is" => "is",
+            _ => return Err(format!("expected a comparison after '{}'", column)),
+        };
+
+        let value = match self.next() {
+            Some(PredicateToken::Literal(value)) => value,
+            Some(PredicateToken::Word(ref word)) if word == "null" => Value::nothing(),
+            Some(PredicateToken::Word(ref word)) if !is_keyword(word) => Value::string(word),
+            _ => return Err(format!("expected a value after '{}'", op)),
+        };
+
+        let test = match op {
+            "=~" | "!~" => {
+                let pattern = value.as_string().map_err(|_| "expected a pattern")?;
+                match Regex::new(&pattern) {
+                    Ok(regex) => Test::Matches(regex, op == "!~"),
+                    Err(e) => return Err(format!("invalid pattern: {}", e)),
+                }
+            }
+            "contains" => Test::Contains(value),
+            "is" => {
+                let type_name = value.as_string().map_err(|_| "expected a type name")?;
+                match TYPE_NAMES.iter().find(|name| **name == type_name) {
+                    Some(name) => Test::Is(name),
+                    None => {
+                        return Err(format!(
+                            "unknown type '{}', expected one of: {}",
+                            type_name,
+                            TYPE_NAMES.join(", ")
+                        ))
+                    }
+                }
+            }
+            op => Test::Compare(Operator::from(op), value),
+        };
+
+        Ok(PredicateExpression::Column(column, test))
+    }
+}
+
+/// A condition such as `size > 1mb and name =~ '.rs$'`, parsed once and tested against each row
+#[derive(Debug, Clone)]
+pub struct Predicate {
+    expression: PredicateExpression,
+    span: Span,
+    insensitive: bool,
+}
+
+impl Predicate {
+    pub fn parse(source: &Tagged<String>) -> Result<Predicate, ShellError> {
+        let parsed = tokenize(&source.item).and_then(|tokens| {
+            let mut parser = PredicateParser { tokens, pos: 0 };
+            let expression = parser.or()?;
+
+            match parser.next() {
+                None => Ok(expression),
+                Some(_) => Err("unexpected input after the condition".to_string()),
+            }
+        });
+
+        match parsed {
+            Ok(expression) => Ok(Predicate {
+                expression,
+                span: source.span(),
+                insensitive: false,
+            }),
+            Err(reason) => Err(ShellError::labeled_error(
+                "Could not parse condition",
+                reason,
+                source.span(),
+            )),
+        }
+    }
+
+    /// Makes comparisons and `contains` ignore case when comparing text. Patterns
+    /// keep their own case rules, so use `(?i)` in them instead
+    pub fn insensitive(self) -> Predicate {
+        Predicate {
+            insensitive: true,
+            ..self
+        }
+    }
+
+    // Text contains a piece of text, and a list contains one of its items
+    fn contains(&self, column: &str, value: &Value, needle: &Value) -> Result<bool, ShellError> {
+        let fold = |s: &str| {
+            if self.insensitive {
+                s.to_lowercase()
+            } else {
+                s.to_string()
+            }
+        };
+
+        match value {
+            Value::Primitive(Primitive::String(s)) => match needle.as_string() {
+                Ok(needle) => Ok(fold(s).contains(&fold(&needle))),
+                Err(_) => Ok(false),
+            },
+            Value::List(items) => Ok(items.iter().any(|item| match (&item.item, needle) {
+                (
+                    Value::Primitive(Primitive::String(item)),
+                    Value::Primitive(Primitive::String(needle)),
+                ) => fold(item) == fold(needle),
+                (item, needle) => item.compare(&Operator::Equal, needle).unwrap_or(false),
+            })),
+            // Nothing holds nothing, so it can't contain the needle
+            Value::Primitive(Primitive::Nothing) => Ok(false),
+            other => Err(ShellError::labeled_error(
+                "Can only look inside text and lists",
+                format!("'{}' is {}", column, other.type_name()),
+                self.span,
+            )),
+        }
+    }
+
+    pub fn matches(&self, row: &Tagged<Value>) -> Result<bool, ShellError> {
+        self.evaluate(&self.expression, row)
+    }
+
+    fn evaluate(
+        &self,
+        expression: &PredicateExpression,
+        row: &Tagged<Value>,
+    ) -> Result<bool, ShellError> {
+        match expression {
+            PredicateExpression::And(left, right) => {
+                Ok(self.evaluate(left, row)? && self.evaluate(right, row)?)
+            }
+            PredicateExpression::Or(left, right) => {
+                Ok(self.evaluate(left, row)? || self.evaluate(right, row)?)
+            }
+            PredicateExpression::Not(inner) => Ok(!self.evaluate(inner, row)?),
+            PredicateExpression::Column(column, test) => {
+                // A row without the column holds nothing there, the same as
+                // an empty cell
+                let nothing = Value::nothing();
+                let value = match row.get_data_by_path(row.tag(), column) {
+                    Some(value) => value.item,
+                    None => &nothing,
+                };
+
+                match test {
+                    Test::Compare(op, expected) => {
+                        let folded = match (self.insensitive, value, expected) {
+                            (
+                                true,
+                                Value::Primitive(Primitive::String(left)),
+                                Value::Primitive(Primitive::String(right)),
+                            ) => Some((
+                                Value::string(left.to_lowercase()),
+                                Value::string(right.to_lowercase()),
+                            )),
+                            _ => None,
+                        };
+
+                        let compared = match folded {
+                            Some((left, right)) => left.compare(op, &right),
+                            None => value.compare(op, expected),
+                        };
+
+                        compared.map_err(|(left, right)| {
+                            ShellError::labeled_error(
+                                format!("Could not compare {} with {}", left, right),
+                                format!("'{}' is {}", column, left),
+                                self.span,
+                            )
+                        })
+                    }
+                    Test::Contains(needle) => self.contains(column, value, needle),
+                    Test::Is(type_name) => Ok(is_type(value, type_name)),
+                    Test::Matches(_, _) if *value == nothing => Ok(false),
+                    Test::Matches(regex, negated) => match value.as_string() {
+                        Ok(s) => Ok(regex.is_match(&s) != *negated),
+                        Err(_) => Err(ShellError::labeled_error(
+                            "Patterns can only match text",
+                            format!("'{}' is {}", column, value.type_name()),
+                            self.span,
+                        )),
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// The condition taken by where, skip-while and take-while: either a block or a quoted predicate
+pub enum Condition {
+    Block(value::Block),
+    Predicate(Predicate),
+}
+
+impl Condition {
+    pub fn from_value(condition: Tagged<Value>) -> Result<Condition, ShellError> {
+        let tag = condition.tag();
+
+        match condition.item {
+            Value::Block(block) => Ok(Condition::Block(block)),
+            Value::Primitive(Primitive::String(s)) => {
+                Ok(Condition::Predicate(Predicate::parse(&s.tagged(tag))?))
+            }
+            _ => Err(ShellError::labeled_error(
+                "Expected a condition",
+                "requires a condition",
+                tag.span,
+            )),
+        }
+    }
+
+    /// Quoted predicates compare text without regard to case. Blocks are
+    /// evaluated as they are written, so asking them to ignore case is an error
+    pub fn insensitive(self, span: Span) -> Result<Condition, ShellError> {
+        match self {
+            Condition::Predicate(predicate) => Ok(Condition::Predicate(predicate.insensitive())),
+            Condition::Block(_) => Err(ShellError::labeled_error(
+                "Blocks can't ignore case",
+                "--insensitive only works with quoted conditions",
+                span,
+            )),
+        }
+    }
+
+    pub fn test(&self, row: &Tagged<Value>) -> Result<bool, ShellError> {
+        match self {
+            Condition::Block(block) => Ok(block.invoke(row)?.is_true()),
+            Condition::Predicate(predicate) => predicate.matches(row),
+        }
+    }
+}
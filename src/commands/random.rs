@@ -0,0 +1,173 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use rand::distributions::Uniform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::{Builder, Variant, Version};
+
+pub struct Random;
+
+#[derive(Deserialize)]
+pub struct RandomArgs {
+    kind: Tagged<String>,
+    first: Option<Tagged<Value>>,
+    second: Option<Tagged<Value>>,
+    seed: Option<Tagged<i64>>,
+}
+
+impl WholeStreamCommand for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random")
+            .required("kind", SyntaxType::Any)
+            .optional("first", SyntaxType::Any)
+            .optional("second", SyntaxType::Any)
+            .named("seed", SyntaxType::Number)
+    }
+
+    fn usage(&self) -> &str {
+        "Generate a random integer, decimal, bool, uuid or dice roll, the same one each time when given '--seed'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, random)?.run()
+    }
+}
+
+fn expect_int(value: &Option<Tagged<Value>>, name: &str, span: Span) -> Result<i64, ShellError> {
+    match value {
+        Some(value) => value.as_i64().map_err(|_| {
+            ShellError::labeled_error("Value is not a number", "expected integer", value.span())
+        }),
+        None => Err(ShellError::labeled_error(
+            format!("random integer requires a {}", name),
+            "needs parameter",
+            span,
+        )),
+    }
+}
+
+// Each die is drawn in turn, so the count is capped to keep a roll quick
+const MAX_DICE: u32 = 10_000;
+
+// Dice are written as how many to roll and how many sides each has, eg) 3d6
+fn parse_dice(s: &str) -> Option<(u32, i64)> {
+    let mut parts = s.splitn(2, 'd');
+    let count = parts.next()?.parse().ok()?;
+    let sides = parts.next()?.parse().ok()?;
+
+    if count > 0 && sides > 0 {
+        Some((count, sides))
+    } else {
+        None
+    }
+}
+
+fn random(
+    RandomArgs {
+        kind,
+        first,
+        second,
+        seed,
+    }: RandomArgs,
+    RunnableContext { name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    // Every value in one call is drawn from the same generator, so a seed
+    // pins down all of them
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed.item as u64),
+        None => StdRng::from_entropy(),
+    };
+
+    let value = match kind.item.as_str() {
+        "integer" => {
+            let min = expect_int(&first, "minimum", name)?;
+            let max = expect_int(&second, "maximum", name)?;
+
+            if min > max {
+                return Err(ShellError::labeled_error(
+                    "Minimum is larger than the maximum",
+                    format!("{} is larger than {}", min, max),
+                    kind.span(),
+                ));
+            }
+
+            Value::int(rng.sample(Uniform::new_inclusive(min, max)))
+        }
+        "decimal" => Value::Primitive(Primitive::from(rng.gen::<f64>())),
+        "bool" => Value::boolean(rng.gen::<bool>()),
+        "uuid" => {
+            let uuid = Builder::from_bytes(rng.gen())
+                .set_variant(Variant::RFC4122)
+                .set_version(Version::Random)
+                .build();
+
+            Value::string(uuid.to_hyphenated().to_string())
+        }
+        "dice" => {
+            let dice = match &first {
+                Some(dice) => dice,
+                None => {
+                    return Err(ShellError::labeled_error(
+                        "random dice requires the dice to roll",
+                        "needs parameter",
+                        name,
+                    ))
+                }
+            };
+
+            match parse_dice(&dice.as_string()?) {
+                Some((count, _)) if count > MAX_DICE => {
+                    return Err(ShellError::labeled_error(
+                        "Too many dice",
+                        format!("at most {} dice can be rolled at once", MAX_DICE),
+                        dice.span(),
+                    ))
+                }
+                Some((count, sides)) => {
+                    let die = Uniform::new_inclusive(1, sides);
+                    let total =
+                        (0..count).try_fold(0i64, |total, _| total.checked_add(rng.sample(die)));
+
+                    match total {
+                        Some(total) => Value::int(total),
+                        None => {
+                            return Err(ShellError::labeled_error(
+                                "Dice roll is too large",
+                                "the total does not fit in an integer",
+                                dice.span(),
+                            ))
+                        }
+                    }
+                }
+                None => {
+                    return Err(ShellError::labeled_error(
+                        "Could not parse dice",
+                        "expected dice like 3d6",
+                        dice.span(),
+                    ))
+                }
+            }
+        }
+        _ => {
+            return Err(ShellError::labeled_error(
+                "Unknown kind of random value",
+                "expected integer, decimal, bool, uuid or dice",
+                kind.span(),
+            ))
+        }
+    };
+
+    let mut random_out = VecDeque::new();
+    random_out.push_back(value.simple_spanned(name));
+
+    Ok(random_out.to_output_stream())
+}
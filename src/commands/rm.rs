@@ -10,7 +10,9 @@ pub struct Remove;
 #[derive(Deserialize)]
 pub struct RemoveArgs {
     pub target: Tagged<PathBuf>,
+    pub rest: Vec<Tagged<PathBuf>>,
     pub recursive: Tagged<bool>,
+    pub trash: Tagged<bool>,
 }
 
 impl PerItemCommand for Remove {
@@ -21,11 +23,13 @@ impl PerItemCommand for Remove {
     fn signature(&self) -> Signature {
         Signature::build("rm")
             .required("path", SyntaxType::Path)
+            .rest(SyntaxType::Path)
             .switch("recursive")
+            .switch("trash")
     }
 
     fn usage(&self) -> &str {
-        "Remove a file, (for removing directory append '--recursive')"
+        "Remove files, (for removing directory append '--recursive', to use the trash append '--trash')"
     }
 
     fn run(
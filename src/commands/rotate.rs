@@ -0,0 +1,124 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+
+pub struct Rotate;
+
+#[derive(Deserialize)]
+pub struct RotateArgs {
+    clockwise: bool,
+    #[serde(rename(deserialize = "counter-clockwise"))]
+    counter_clockwise: bool,
+}
+
+impl WholeStreamCommand for Rotate {
+    fn name(&self) -> &str {
+        "rotate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("rotate")
+            .switch("clockwise")
+            .switch("counter-clockwise")
+    }
+
+    fn usage(&self) -> &str {
+        "Rotate the table 90 degrees, clockwise unless '--counter-clockwise' is given."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, rotate)?.run()
+    }
+}
+
+// Lays the rows out as a matrix, using every column seen in the order it was
+// first seen. Cells missing from ragged rows are filled with nothing.
+fn to_matrix(rows: Vec<Tagged<Value>>, name: Span) -> Result<Vec<Vec<Tagged<Value>>>, ShellError> {
+    let mut columns: Vec<String> = vec![];
+
+    for row in rows.iter() {
+        match &row.item {
+            Value::Object(o) => {
+                for key in o.entries.keys() {
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+            _ => {
+                return Err(ShellError::type_mismatch(
+                    "a table",
+                    row.tagged_type_name(),
+                    name,
+                ))
+            }
+        }
+    }
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| match row.get_data_by_key(column) {
+                    Some(value) => value.clone(),
+                    None => Value::Primitive(Primitive::Nothing).tagged(row.tag()),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+fn rotate(
+    RotateArgs {
+        clockwise,
+        counter_clockwise,
+    }: RotateArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    if clockwise && counter_clockwise {
+        return Err(ShellError::labeled_error(
+            "Can only rotate in one direction",
+            "given both --clockwise and --counter-clockwise",
+            name,
+        ));
+    }
+
+    let stream = async_stream_block! {
+        let rows: Vec<Tagged<Value>> = input.values.collect().await;
+
+        match to_matrix(rows, name) {
+            Ok(matrix) => {
+                let height = matrix.len();
+                let width = matrix.first().map(|row| row.len()).unwrap_or(0);
+
+                // Each column of the table becomes a row: clockwise reads the
+                // columns left to right from the bottom row up, counter-clockwise
+                // reads them right to left from the top row down
+                for column in 0..width {
+                    let mut rotated = TaggedDictBuilder::new(name);
+
+                    for row in 0..height {
+                        let cell = if counter_clockwise {
+                            &matrix[row][width - 1 - column]
+                        } else {
+                            &matrix[height - 1 - row][column]
+                        };
+
+                        rotated.insert_tagged(format!("Column{}", row + 1), cell.clone());
+                    }
+
+                    yield ReturnSuccess::value(rotated.into_tagged_value());
+                }
+            }
+            Err(e) => yield Err(e),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,127 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{Primitive, Value};
+use crate::prelude::*;
+use rust_decimal::RoundingStrategy;
+
+pub struct Round;
+
+#[derive(Deserialize)]
+pub struct RoundArgs {
+    column: Tagged<String>,
+    places: Tagged<i64>,
+    mode: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Round {
+    fn name(&self) -> &str {
+        "round"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("round")
+            .required("column", SyntaxType::Member)
+            .required("places", SyntaxType::Number)
+            .named("mode", SyntaxType::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Round a column of decimals to the given number of places."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, round)?.run()
+    }
+}
+
+fn round(
+    RoundArgs {
+        column,
+        places,
+        mode,
+    }: RoundArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    // RoundingStrategy isn't Copy, so each row builds its own from this
+    let half_even = match mode {
+        None => false,
+        Some(Tagged { item, tag }) => match item.as_str() {
+            "half-up" => false,
+            "half-even" => true,
+            _ => {
+                return Err(ShellError::labeled_error(
+                    "Unknown rounding mode",
+                    "expected half-up or half-even",
+                    tag.span,
+                ))
+            }
+        },
+    };
+
+    let places = match places {
+        Tagged { item, tag } if item < 0 || item > 28 => {
+            return Err(ShellError::labeled_error(
+                "Can not round to that many places",
+                "expected 0 to 28 places",
+                tag.span,
+            ))
+        }
+        Tagged { item, .. } => item as u32,
+    };
+
+    Ok(input
+        .values
+        .map(move |row| {
+            let value = match row.get_data_by_path(row.tag(), &column.item) {
+                Some(value) => value,
+                None => {
+                    return Err(ShellError::labeled_error_with_secondary(
+                        "Could not find column to round",
+                        format!("missing '{}'", column.item),
+                        column.span(),
+                        "row originates from here",
+                        row.span(),
+                    ))
+                }
+            };
+
+            // Rounding stays in decimals, so no precision is lost through f64
+            let rounded = match value.item {
+                Value::Primitive(Primitive::Decimal(d)) => {
+                    let strategy = if half_even {
+                        RoundingStrategy::BankersRounding
+                    } else {
+                        RoundingStrategy::RoundHalfUp
+                    };
+
+                    Value::Primitive(Primitive::Decimal(
+                        d.round_dp_with_strategy(places, strategy),
+                    ))
+                }
+                Value::Primitive(Primitive::Int(_)) => value.item.clone(),
+                other => {
+                    return Err(ShellError::labeled_error_with_secondary(
+                        "Can only round numbers",
+                        format!("'{}' is {}", column.item, other.type_name()),
+                        column.span(),
+                        "row originates from here",
+                        row.span(),
+                    ))
+                }
+            };
+
+            match row.replace_data_at_path(row.tag(), &column.item, rounded) {
+                Some(row) => ReturnSuccess::value(row),
+                None => Err(ShellError::labeled_error(
+                    "Could not round column",
+                    "requires a table",
+                    column.span(),
+                )),
+            }
+        })
+        .to_output_stream())
+}
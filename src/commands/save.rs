@@ -2,6 +2,7 @@ use crate::commands::{UnevaluatedCallInfo, WholeStreamCommand};
 use crate::errors::ShellError;
 use crate::object::Value;
 use crate::prelude::*;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 pub struct Save;
@@ -115,12 +116,30 @@ fn save(
                         }
                     };
                     let mut result = converter.run(new_args.with_input(input), &registry);
-                    let result_vec: Vec<Result<ReturnSuccess, ShellError>> = result.drain_vec().await;
-                    let mut result_string = String::new();
-                    for res in result_vec {
-                        match res {
+
+                    // Converters to binary formats may hand back their output in
+                    // several chunks, which are written to the file as they arrive
+                    let created = std::fs::File::create(&full_path);
+                    let mut file = match created {
+                        Ok(file) => file,
+                        Err(e) => {
+                            let error = ShellError::string(e.to_string());
+                            yield Err(error);
+                            return;
+                        }
+                    };
+
+                    while let Some(res) = result.values.next().await {
+                        let written = match res {
                             Ok(ReturnSuccess::Value(Tagged { item: Value::Primitive(Primitive::String(s)), .. })) => {
-                                result_string.push_str(&s);
+                                file.write_all(s.as_bytes())
+                            }
+                            Ok(ReturnSuccess::Value(Tagged { item: Value::Binary(b), .. })) => {
+                                file.write_all(&b)
+                            }
+                            Err(e) => {
+                                yield Err(e);
+                                continue;
                             }
                             _ => {
                                 yield Err(ShellError::labeled_error(
@@ -128,10 +147,18 @@ fn save(
                                     "unexpected data during save",
                                     name_span,
                                 ));
+                                continue;
                             },
+                        };
+
+                        if let Err(e) = written {
+                            let error = ShellError::string(e.to_string());
+                            yield Err(error);
+                            return;
                         }
                     }
-                    Ok(result_string)
+
+                    return;
                 } else {
                     let mut result_string = String::new();
                     for res in input {
@@ -148,7 +175,7 @@ fn save(
                             },
                         }
                     }
-                    Ok(result_string)
+                    Ok(result_string.into_bytes())
                 }
             } else {
                 let mut result_string = String::new();
@@ -166,10 +193,10 @@ fn save(
                         },
                     }
                 }
-                Ok(result_string)
+                Ok(result_string.into_bytes())
             }
         } else {
-            string_from(&input)
+            string_from(&input).map(String::into_bytes)
         };
 
         match content {
@@ -0,0 +1,30 @@
+use crate::commands::pick::pick;
+use crate::commands::WholeStreamCommand;
+use crate::context::CommandRegistry;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+// The same as pick, under the name other shells use for it
+pub struct Select;
+
+impl WholeStreamCommand for Select {
+    fn name(&self) -> &str {
+        "select"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("select").rest(SyntaxType::Member)
+    }
+
+    fn usage(&self) -> &str {
+        "Keep only the given columns, in the given order."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, pick)?.run()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{Primitive, Value};
+use crate::prelude::*;
+
+pub struct Seq;
+
+#[derive(Deserialize)]
+pub struct SeqArgs {
+    start: Tagged<Value>,
+    end: Tagged<Value>,
+    step: Option<Tagged<Value>>,
+}
+
+impl WholeStreamCommand for Seq {
+    fn name(&self) -> &str {
+        "seq"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("seq")
+            .required("start", SyntaxType::Number)
+            .required("end", SyntaxType::Number)
+            .optional("step", SyntaxType::Number)
+    }
+
+    fn usage(&self) -> &str {
+        "Output the numbers from start to end, counting by step (1 by default)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, seq)?.run()
+    }
+}
+
+fn as_number(value: &Tagged<Value>) -> Result<Number, ShellError> {
+    match value.item {
+        Value::Primitive(Primitive::Int(i)) => Ok(Number::Int(i)),
+        Value::Primitive(Primitive::Decimal(d)) => Ok(Number::Decimal(d)),
+        _ => Err(ShellError::type_mismatch(
+            "a number",
+            value.tagged_type_name(),
+            value.span(),
+        )),
+    }
+}
+
+fn as_decimal(n: Number) -> Decimal {
+    match n {
+        Number::Int(i) => Decimal::from(i),
+        Number::Decimal(d) => d,
+    }
+}
+
+fn seq(
+    SeqArgs { start, end, step }: SeqArgs,
+    RunnableContext { name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let start = as_number(&start)?;
+    let end = as_number(&end)?;
+
+    let (step, step_span) = match step {
+        Some(step) => (as_number(&step)?, step.span()),
+        None => (Number::Int(1), name),
+    };
+
+    let zero = Decimal::new(0, 0);
+    let ascending = as_decimal(step) > zero;
+
+    if as_decimal(step) == zero {
+        return Err(ShellError::labeled_error(
+            "Can not count by a step of zero",
+            "step must not be zero",
+            step_span,
+        ));
+    }
+
+    // The numbers are produced on demand, so long ranges are never held in memory
+    match (start, end, step) {
+        (Number::Int(start), Number::Int(end), Number::Int(step)) => {
+            let numbers = std::iter::successors(Some(start), move |n| n.checked_add(step))
+                .take_while(move |n| if ascending { *n <= end } else { *n >= end })
+                .map(move |n| ReturnSuccess::value(Value::int(n).simple_spanned(name)));
+
+            Ok(futures::stream::iter(numbers).to_output_stream())
+        }
+        (start, end, step) => {
+            let (start, end, step) = (as_decimal(start), as_decimal(end), as_decimal(step));
+
+            let numbers = std::iter::successors(Some(start), move |n| n.checked_add(step))
+                .take_while(move |n| if ascending { *n <= end } else { *n >= end })
+                .map(move |n| {
+                    ReturnSuccess::value(Value::number(Number::Decimal(n)).simple_spanned(name))
+                });
+
+            Ok(futures::stream::iter(numbers).to_output_stream())
+        }
+    }
+}
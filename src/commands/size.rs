@@ -34,12 +34,10 @@ fn size(args: CommandArgs, _registry: &CommandRegistry) -> Result<OutputStream,
         .values
         .map(move |v| match v.item {
             Value::Primitive(Primitive::String(ref s)) => ReturnSuccess::value(count(s, v.tag())),
-            _ => Err(ShellError::labeled_error_with_secondary(
-                "Expected a string from pipeline",
-                "requires string input",
+            _ => Err(ShellError::type_mismatch(
+                "string input",
+                v.tagged_type_name(),
                 span,
-                "value originates from here",
-                v.span(),
             )),
         })
         .to_output_stream())
@@ -60,7 +58,7 @@ fn count(contents: &str, tag: impl Into<Tag>) -> Tagged<Value> {
                 lines += 1;
                 end_of_word = true;
             }
-            ' ' => end_of_word = true,
+            c if c.is_whitespace() => end_of_word = true,
             _ => {
                 if end_of_word {
                     words += 1;
@@ -76,7 +74,7 @@ fn count(contents: &str, tag: impl Into<Tag>) -> Tagged<Value> {
     dict.insert("lines", Value::int(lines));
     dict.insert("words", Value::int(words));
     dict.insert("chars", Value::int(chars));
-    dict.insert("max length", Value::int(bytes));
+    dict.insert("bytes", Value::int(bytes));
 
     dict.into_tagged_value()
 }
@@ -1,3 +1,4 @@
+use crate::commands::predicate::Condition;
 use crate::commands::WholeStreamCommand;
 use crate::errors::ShellError;
 use crate::prelude::*;
@@ -6,7 +7,7 @@ pub struct SkipWhile;
 
 #[derive(Deserialize)]
 pub struct SkipWhileArgs {
-    condition: value::Block,
+    condition: Tagged<Value>,
 }
 
 impl WholeStreamCommand for SkipWhile {
@@ -37,16 +38,30 @@ pub fn skip_while(
     SkipWhileArgs { condition }: SkipWhileArgs,
     RunnableContext { input, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
-    let objects = input.values.skip_while(move |item| {
-        let result = condition.invoke(&item);
+    let condition = Condition::from_value(condition)?;
 
-        let return_value = match result {
-            Ok(ref v) if v.is_true() => true,
-            _ => false,
-        };
+    // A row the condition can't be tested against ends the command with its error
+    let stream = async_stream_block! {
+        let mut rows = input.values;
+        let mut skipping = true;
 
-        futures::future::ready(return_value)
-    });
+        while let Some(item) = rows.next().await {
+            if skipping {
+                let matched = condition.test(&item);
 
-    Ok(objects.from_input_stream())
+                match matched {
+                    Ok(true) => continue,
+                    Ok(false) => skipping = false,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                }
+            }
+
+            yield ReturnSuccess::value(item);
+        }
+    };
+
+    Ok(stream.to_output_stream())
 }
@@ -0,0 +1,94 @@
+use crate::cli::{print_err, process_line, LineResult};
+use crate::commands::WholeStreamCommand;
+use crate::context::Context;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use std::path::PathBuf;
+
+pub struct Source;
+
+#[derive(Deserialize)]
+pub struct SourceArgs {
+    path: Tagged<PathBuf>,
+    #[serde(rename(deserialize = "keep-going"))]
+    keep_going: bool,
+}
+
+impl WholeStreamCommand for Source {
+    fn name(&self) -> &str {
+        "source"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("source")
+            .required("path", SyntaxType::Path)
+            .switch("keep-going")
+    }
+
+    fn usage(&self) -> &str {
+        "Run each line of a file as a pipeline, stopping at the first that fails unless given '--keep-going'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, source)?.run()
+    }
+}
+
+fn source(
+    SourceArgs { path, keep_going }: SourceArgs,
+    RunnableContext {
+        shell_manager,
+        host,
+        commands,
+        source_map,
+        ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let full_path = PathBuf::from(shell_manager.path()).join(&path.item);
+
+    let contents = std::fs::read_to_string(&full_path).map_err(|e| {
+        ShellError::labeled_error("Could not read script", e.to_string(), path.span())
+    })?;
+
+    let stream = async_stream_block! {
+        // Lines run one after another against the same shells, so a `cd` in
+        // one line is where the next line starts from
+        let mut context = Context::from_parts(commands, source_map, host, shell_manager);
+
+        for (index, line) in contents.lines().enumerate() {
+            match process_line(Ok(line.to_string()), &mut context).await {
+                LineResult::Success(_) | LineResult::CtrlC => {}
+                LineResult::Break => {
+                    // The rest of the script is skipped and the shell that
+                    // ran `source` is asked to stop as well
+                    yield ReturnSuccess::action(CommandAction::Exit);
+                    return;
+                }
+                LineResult::Error(line, err) => {
+                    // The error can only point into the line it came from
+                    context.with_host(|host| print_err(err, host, line));
+
+                    if !keep_going {
+                        let error = ShellError::labeled_error(
+                            format!("Script stopped at {}:{}", path.item.display(), index + 1),
+                            format!("line {} failed", index + 1),
+                            path.span(),
+                        );
+                        yield Err(error);
+                        return;
+                    }
+                }
+                LineResult::FatalError(_, err) => {
+                    yield Err(err);
+                    return;
+                }
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
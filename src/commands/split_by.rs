@@ -0,0 +1,70 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct SplitBy;
+
+#[derive(Deserialize)]
+pub struct SplitByArgs {
+    boundary: Tagged<Value>,
+}
+
+impl WholeStreamCommand for SplitBy {
+    fn name(&self) -> &str {
+        "split-by"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("split-by").required("boundary", SyntaxType::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Split the rows into lists at each row matching the boundary block or equal to the boundary value."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, split_by)?.run()
+    }
+}
+
+fn is_boundary(boundary: &Value, row: &Tagged<Value>) -> Result<bool, ShellError> {
+    match boundary {
+        Value::Block(block) => Ok(block.invoke(row)?.is_true()),
+        sentinel => Ok(row.item == *sentinel),
+    }
+}
+
+pub fn split_by(
+    SplitByArgs { boundary }: SplitByArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+        let mut group: Vec<Tagged<Value>> = vec![];
+
+        // Only the group being built is held in memory. The boundary rows
+        // themselves are dropped, and so are groups left empty between them.
+        while let Some(row) = values.next().await {
+            match is_boundary(&boundary.item, &row) {
+                Ok(true) => {
+                    if !group.is_empty() {
+                        let done = std::mem::replace(&mut group, vec![]);
+                        yield ReturnSuccess::value(Value::List(done).simple_spanned(name));
+                    }
+                }
+                Ok(false) => group.push(row),
+                Err(e) => yield Err(e),
+            }
+        }
+
+        if !group.is_empty() {
+            yield ReturnSuccess::value(Value::List(group).simple_spanned(name));
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
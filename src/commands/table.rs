@@ -7,7 +7,9 @@ use futures_async_stream::async_stream_block;
 pub struct Table;
 
 #[derive(Deserialize)]
-pub struct TableArgs {}
+pub struct TableArgs {
+    width: Option<Tagged<i64>>,
+}
 
 impl WholeStreamCommand for Table {
     fn name(&self) -> &str {
@@ -15,11 +17,11 @@ impl WholeStreamCommand for Table {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("table")
+        Signature::build("table").named("width", SyntaxType::Number)
     }
 
     fn usage(&self) -> &str {
-        "View the contents of the pipeline as a table."
+        "Render the contents of the pipeline as a table of text, fitting it to '--width' columns or the terminal."
     }
 
     fn run(
@@ -31,17 +33,30 @@ impl WholeStreamCommand for Table {
     }
 }
 
-pub fn table(_args: TableArgs, context: RunnableContext) -> Result<OutputStream, ShellError> {
+pub fn table(
+    TableArgs { width }: TableArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let width = match width {
+        Some(width) if width.item <= 0 => {
+            return Err(ShellError::labeled_error(
+                "Table width must be positive",
+                "expected a number greater than zero",
+                width.span(),
+            ))
+        }
+        Some(width) => width.item as usize,
+        None => textwrap::termwidth(),
+    };
+
     let stream = async_stream_block! {
-        let input: Vec<Tagged<Value>> = context.input.into_vec().await;
-        if input.len() > 0 {
-            let mut host = context.host.lock().unwrap();
-            let view = TableView::from_list(&input);
-            if let Some(view) = view {
-                handle_unexpected(&mut *host, |host| crate::format::print_view(&view, host));
-            }
+        let input: Vec<Tagged<Value>> = input.into_vec().await;
+
+        // Wide columns are wrapped, and columns that do not fit are cut off
+        if let Some(view) = TableView::from_list_with_width(&input, width) {
+            yield ReturnSuccess::value(Value::string(view.render_string()).simple_spanned(name));
         }
     };
 
-    Ok(OutputStream::new(stream))
+    Ok(stream.to_output_stream())
 }
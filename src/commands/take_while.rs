@@ -0,0 +1,62 @@
+use crate::commands::predicate::Condition;
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct TakeWhile;
+
+#[derive(Deserialize)]
+pub struct TakeWhileArgs {
+    condition: Tagged<Value>,
+}
+
+impl WholeStreamCommand for TakeWhile {
+    fn name(&self) -> &str {
+        "take-while"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("take-while")
+            .required("condition", SyntaxType::Block)
+            .filter()
+    }
+
+    fn usage(&self) -> &str {
+        "Takes rows while the condition matches."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, take_while)?.run()
+    }
+}
+
+pub fn take_while(
+    TakeWhileArgs { condition }: TakeWhileArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let condition = Condition::from_value(condition)?;
+
+    // A row the condition can't be tested against ends the rows taken with its error
+    let stream = async_stream_block! {
+        let mut rows = input.values;
+
+        while let Some(item) = rows.next().await {
+            let matched = condition.test(&item);
+
+            match matched {
+                Ok(true) => yield ReturnSuccess::value(item),
+                Ok(false) => break,
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
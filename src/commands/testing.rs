@@ -0,0 +1,44 @@
+use crate::commands::command::{whole_stream_command, UnevaluatedCallInfo};
+use crate::commands::WholeStreamCommand;
+use crate::context::SourceMap;
+use crate::parser::hir::{self, named::NamedValue, Expression, NamedArguments};
+use crate::prelude::*;
+
+/// Runs a command on the given rows, as if it were the only command in a
+/// pipeline, and collects everything it returns. Only the given switches are
+/// passed; every other argument is left absent.
+pub(crate) fn run_command(
+    command: impl WholeStreamCommand + 'static,
+    switches: &[&str],
+    input: Vec<Tagged<Value>>,
+    registry: &CommandRegistry,
+) -> Vec<ReturnValue> {
+    let mut named = NamedArguments::new();
+    for switch in switches {
+        named.named.insert(
+            switch.to_string(),
+            NamedValue::PresentSwitch(Span::unknown()),
+        );
+    }
+
+    let call = hir::Call::new(
+        Box::new(Expression::synthetic_string(command.name())),
+        None,
+        Some(named),
+    );
+
+    let args = CommandArgs {
+        host: Arc::new(Mutex::new(crate::env::host::BasicHost)),
+        shell_manager: ShellManager::basic(registry.clone()).expect("could not start a shell"),
+        call_info: UnevaluatedCallInfo {
+            args: call,
+            source: Text::from(String::new()),
+            source_map: SourceMap::new(),
+            name_span: Span::unknown(),
+        },
+        input: input.into(),
+    };
+
+    let output = whole_stream_command(command).run(args, registry);
+    futures::executor::block_on(output.values.collect())
+}
@@ -1,11 +1,23 @@
 use crate::commands::WholeStreamCommand;
-use crate::object::{Dictionary, Primitive, Value};
+use crate::errors::CoerceInto;
+use crate::object::{Dictionary, Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
 use bson::{encode_document, oid::ObjectId, spec::BinarySubtype, Bson, Document};
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
 use std::convert::TryInto;
 
 pub struct ToBSON;
 
+#[derive(Deserialize, Clone, Copy)]
+pub struct ToBSONArgs {
+    decimal128: bool,
+    #[serde(rename(deserialize = "utc-aware"))]
+    utc_aware: bool,
+    validate: bool,
+    #[serde(rename(deserialize = "per-document"))]
+    per_document: bool,
+}
+
 impl WholeStreamCommand for ToBSON {
     fn name(&self) -> &str {
         "to-bson"
@@ -13,6 +25,10 @@ impl WholeStreamCommand for ToBSON {
 
     fn signature(&self) -> Signature {
         Signature::build("to-bson")
+            .switch("decimal128")
+            .switch("utc-aware")
+            .switch("validate")
+            .switch("per-document")
     }
 
     fn usage(&self) -> &str {
@@ -24,12 +40,28 @@ impl WholeStreamCommand for ToBSON {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        to_bson(args, registry)
+        args.process(registry, to_bson)?.run()
     }
 }
 
-pub fn value_to_bson_value(v: &Value) -> Bson {
-    match v {
+// Decimals are written as `{ "$numberDecimal": "..." }`, the extended JSON
+// form of a Decimal128, so that no digits are lost on the way to MongoDB.
+fn decimal_to_bson(d: &Decimal) -> Bson {
+    let mut doc = Document::new();
+    doc.insert("$numberDecimal", Bson::String(d.to_string()));
+    Bson::Document(doc)
+}
+
+// Whole numbers too wide for an i64 are written as `{ "$numberLong": "..." }`
+// rather than as a double, which would drop their trailing digits
+fn number_long_to_bson(digits: String) -> Bson {
+    let mut doc = Document::new();
+    doc.insert("$numberLong", Bson::String(digits));
+    Bson::Document(doc)
+}
+
+pub fn value_to_bson_value(v: &Tagged<Value>, args: ToBSONArgs) -> Result<Bson, ShellError> {
+    Ok(match v.item() {
         Value::Primitive(Primitive::Boolean(b)) => Bson::Boolean(*b),
         // FIXME: What about really big decimals?
         Value::Primitive(Primitive::Bytes(decimal)) => Bson::FloatingPoint(
@@ -37,27 +69,47 @@ pub fn value_to_bson_value(v: &Value) -> Bson {
                 .to_f64()
                 .expect("Unimplemented BUG: What about big decimals?"),
         ),
-        Value::Primitive(Primitive::Date(d)) => Bson::UtcDatetime(*d),
+        Value::Primitive(Primitive::Date(d)) => date_to_bson(d, args),
         Value::Primitive(Primitive::EndOfStream) => Bson::Null,
         Value::Primitive(Primitive::BeginningOfStream) => Bson::Null,
-        Value::Primitive(Primitive::Decimal(d)) => Bson::FloatingPoint(d.to_f64().unwrap()),
+        Value::Primitive(Primitive::Decimal(d)) if args.decimal128 => decimal_to_bson(d),
+        Value::Primitive(Primitive::Decimal(d)) if d.trunc() == *d && d.to_i64().is_none() => {
+            number_long_to_bson(d.to_string())
+        }
+        Value::Primitive(Primitive::Decimal(d)) => {
+            match (*d).tagged(v.tag).coerce_into("converting to BSON double") {
+                Ok(f) => Bson::FloatingPoint(f),
+                Err(_) => decimal_to_bson(d),
+            }
+        }
         Value::Primitive(Primitive::Int(i)) => Bson::I64(*i),
+        Value::Primitive(Primitive::BigInt(i)) => number_long_to_bson(i.to_string()),
         Value::Primitive(Primitive::Nothing) => Bson::Null,
         Value::Primitive(Primitive::String(s)) => Bson::String(s.clone()),
         Value::Primitive(Primitive::Path(s)) => Bson::String(s.display().to_string()),
-        Value::List(l) => Bson::Array(l.iter().map(|x| value_to_bson_value(x)).collect()),
-        Value::Block(_) => Bson::Null,
+        Value::List(l) => Bson::Array(
+            l.iter()
+                .map(|x| value_to_bson_value(x, args))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        Value::Block(_) => {
+            return Err(ShellError::labeled_error(
+                "Blocks can not be converted to BSON",
+                "block originates from here",
+                v.span(),
+            ))
+        }
         Value::Binary(b) => Bson::Binary(BinarySubtype::Generic, b.clone()),
-        Value::Object(o) => object_value_to_bson(o),
-    }
+        Value::Object(o) => object_value_to_bson(o, args)?,
+    })
 }
 
 // object_value_to_bson handles all Objects, even those that correspond to special
 // types (things like regex or javascript code).
-fn object_value_to_bson(o: &Dictionary) -> Bson {
+fn object_value_to_bson(o: &Dictionary, args: ToBSONArgs) -> Result<Bson, ShellError> {
     let mut it = o.entries.iter();
     if it.len() > 2 {
-        return generic_object_value_to_bson(o);
+        return generic_object_value_to_bson(o, args);
     }
     match it.next() {
         Some((regex, tagged_regex_value)) if regex == "$regex" => match it.next() {
@@ -65,12 +117,12 @@ fn object_value_to_bson(o: &Dictionary) -> Bson {
                 let r: Result<String, _> = tagged_regex_value.try_into();
                 let opts: Result<String, _> = tagged_opts_value.try_into();
                 if r.is_err() || opts.is_err() {
-                    generic_object_value_to_bson(o)
+                    generic_object_value_to_bson(o, args)
                 } else {
-                    Bson::RegExp(r.unwrap(), opts.unwrap())
+                    Ok(Bson::RegExp(r.unwrap(), opts.unwrap()))
                 }
             }
-            _ => generic_object_value_to_bson(o),
+            _ => generic_object_value_to_bson(o, args),
         },
         Some((javascript, tagged_javascript_value)) if javascript == "$javascript" => {
             match it.next() {
@@ -78,32 +130,32 @@ fn object_value_to_bson(o: &Dictionary) -> Bson {
                     let js: Result<String, _> = tagged_javascript_value.try_into();
                     let s: Result<&Dictionary, _> = tagged_scope_value.try_into();
                     if js.is_err() || s.is_err() {
-                        generic_object_value_to_bson(o)
+                        generic_object_value_to_bson(o, args)
                     } else {
-                        if let Bson::Document(doc) = object_value_to_bson(s.unwrap()) {
-                            Bson::JavaScriptCodeWithScope(js.unwrap(), doc)
+                        if let Bson::Document(doc) = object_value_to_bson(s.unwrap(), args)? {
+                            Ok(Bson::JavaScriptCodeWithScope(js.unwrap(), doc))
                         } else {
-                            generic_object_value_to_bson(o)
+                            generic_object_value_to_bson(o, args)
                         }
                     }
                 }
                 None => {
                     let js: Result<String, _> = tagged_javascript_value.try_into();
                     if js.is_err() {
-                        generic_object_value_to_bson(o)
+                        generic_object_value_to_bson(o, args)
                     } else {
-                        Bson::JavaScriptCode(js.unwrap())
+                        Ok(Bson::JavaScriptCode(js.unwrap()))
                     }
                 }
-                _ => generic_object_value_to_bson(o),
+                _ => generic_object_value_to_bson(o, args),
             }
         }
         Some((timestamp, tagged_timestamp_value)) if timestamp == "$timestamp" => {
             let ts: Result<i64, _> = tagged_timestamp_value.try_into();
             if ts.is_err() {
-                generic_object_value_to_bson(o)
+                generic_object_value_to_bson(o, args)
             } else {
-                Bson::TimeStamp(ts.unwrap())
+                Ok(Bson::TimeStamp(ts.unwrap()))
             }
         }
         Some((binary_subtype, tagged_binary_subtype_value))
@@ -114,36 +166,84 @@ fn object_value_to_bson(o: &Dictionary) -> Bson {
                     let bst = get_binary_subtype(tagged_binary_subtype_value);
                     let bin: Result<Vec<u8>, _> = tagged_bin_value.try_into();
                     if bst.is_none() || bin.is_err() {
-                        generic_object_value_to_bson(o)
+                        generic_object_value_to_bson(o, args)
                     } else {
-                        Bson::Binary(bst.unwrap(), bin.unwrap())
+                        Ok(Bson::Binary(bst.unwrap(), bin.unwrap()))
                     }
                 }
-                _ => generic_object_value_to_bson(o),
+                _ => generic_object_value_to_bson(o, args),
             }
         }
         Some((object_id, tagged_object_id_value)) if object_id == "$object_id" => {
             let obj_id: Result<String, _> = tagged_object_id_value.try_into();
             if obj_id.is_err() {
-                generic_object_value_to_bson(o)
+                generic_object_value_to_bson(o, args)
             } else {
                 let obj_id = ObjectId::with_string(&obj_id.unwrap());
                 if obj_id.is_err() {
-                    generic_object_value_to_bson(o)
+                    generic_object_value_to_bson(o, args)
                 } else {
-                    Bson::ObjectId(obj_id.unwrap())
+                    Ok(Bson::ObjectId(obj_id.unwrap()))
                 }
             }
         }
+        Some((number_long, tagged_number_long_value)) if number_long == "$numberLong" => {
+            let n: Result<String, _> = tagged_number_long_value.try_into();
+            match n.ok().and_then(|n| n.parse().ok()) {
+                Some(n) => Ok(Bson::I64(n)),
+                None => generic_object_value_to_bson(o, args),
+            }
+        }
+        Some((date, tagged_date_value)) if date == "$date" => match bson_date(tagged_date_value) {
+            Some(d) => Ok(date_to_bson(&d, args)),
+            None => generic_object_value_to_bson(o, args),
+        },
         Some((symbol, tagged_symbol_value)) if symbol == "$symbol" => {
             let sym: Result<String, _> = tagged_symbol_value.try_into();
             if sym.is_err() {
-                generic_object_value_to_bson(o)
+                generic_object_value_to_bson(o, args)
             } else {
-                Bson::Symbol(sym.unwrap())
+                Ok(Bson::Symbol(sym.unwrap()))
             }
         }
-        _ => generic_object_value_to_bson(o),
+        _ => generic_object_value_to_bson(o, args),
+    }
+}
+
+// Dates are always held in UTC, so an offset such as +02:00 was already
+// applied when the date was read and the stored instant is the same. BSON
+// dates only count milliseconds, though, so anything finer is cut off unless
+// --utc-aware asks for it to be kept as {"$date": "<RFC 3339 in UTC>"}.
+fn date_to_bson(d: &DateTime<Utc>, args: ToBSONArgs) -> Bson {
+    if args.utc_aware && d.timestamp_subsec_nanos() % 1_000_000 != 0 {
+        let mut doc = Document::new();
+        doc.insert(
+            "$date",
+            Bson::String(d.to_rfc3339_opts(SecondsFormat::Nanos, true)),
+        );
+        return Bson::Document(doc);
+    }
+
+    Bson::UtcDatetime(Utc.timestamp_millis(d.timestamp_millis()))
+}
+
+// MongoDB extended JSON writes dates as {"$date": "2024-01-02T03:04:05Z"}, as
+// milliseconds since the epoch, or as {"$date": {"$numberLong": "<millis>"}}
+fn bson_date(tagged_value: &Tagged<Value>) -> Option<DateTime<Utc>> {
+    match tagged_value.item() {
+        Value::Primitive(Primitive::Date(d)) => Some(*d),
+        Value::Primitive(Primitive::String(s)) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|d| d.with_timezone(&Utc)),
+        Value::Primitive(Primitive::Int(millis)) => Utc.timestamp_millis_opt(*millis).single(),
+        Value::Object(o) => match o.get_data_by_key("$numberLong").map(|v| v.item()) {
+            Some(Value::Primitive(Primitive::String(millis))) if o.entries.len() == 1 => millis
+                .parse()
+                .ok()
+                .and_then(|millis| Utc.timestamp_millis_opt(millis).single()),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -165,12 +265,14 @@ fn get_binary_subtype<'a>(tagged_value: &'a Tagged<Value>) -> Option<BinarySubty
 
 // generic_object_value_bson handles any Object that does not
 // correspond to a special bson type (things like regex or javascript code).
-fn generic_object_value_to_bson(o: &Dictionary) -> Bson {
+// Both Dictionary and bson's Document keep insertion order, so the encoded
+// keys always come out in the same order as the row's columns.
+fn generic_object_value_to_bson(o: &Dictionary, args: ToBSONArgs) -> Result<Bson, ShellError> {
     let mut doc = Document::new();
     for (k, v) in o.entries.iter() {
-        doc.insert(k.clone(), value_to_bson_value(v));
+        doc.insert(k.clone(), value_to_bson_value(v, args)?);
     }
-    Bson::Document(doc)
+    Ok(Bson::Document(doc))
 }
 
 fn shell_encode_document(
@@ -188,53 +290,253 @@ fn shell_encode_document(
     }
 }
 
+// Each document is encoded into its own chunk, and the chunks written one after
+// another are the whole encoding. A list is encoded one item at a time, as the
+// chunks are asked for, so an empty list writes nothing at all rather than failing.
+fn bson_value_to_chunks(
+    bson: Bson,
+    span: Span,
+) -> impl Iterator<Item = Result<Vec<u8>, ShellError>> {
+    let documents = match bson {
+        Bson::Array(a) => a,
+        other => vec![other],
+    };
+
+    documents.into_iter().map(move |v| match v {
+        Bson::Document(d) => {
+            let mut out = Vec::new();
+            shell_encode_document(&mut out, d, span)?;
+            Ok(out)
+        }
+        _ => Err(ShellError::labeled_error(
+            format!("All top level values must be Documents, got {:?}", v),
+            "requires BSON-compatible document",
+            span,
+        )),
+    })
+}
+
 fn bson_value_to_bytes(bson: Bson, span: Span) -> Result<Vec<u8>, ShellError> {
-    let mut out = Vec::new();
-    match bson {
-        Bson::Array(a) => {
-            for v in a.into_iter() {
-                match v {
-                    Bson::Document(d) => shell_encode_document(&mut out, d, span)?,
-                    _ => {
-                        return Err(ShellError::labeled_error(
-                            format!("All top level values must be Documents, got {:?}", v),
-                            "requires BSON-compatible document",
-                            span,
-                        ))
-                    }
-                }
+    Ok(bson_value_to_chunks(bson, span)
+        .collect::<Result<Vec<_>, _>>()?
+        .concat())
+}
+
+// With --validate, each row becomes {valid: true}, or {valid: false} with the
+// error and its label, instead of the encoded bytes
+fn validation_row(encoded: Result<Vec<u8>, ShellError>, tag: Tag) -> Tagged<Value> {
+    let mut row = TaggedDictBuilder::new(tag);
+
+    match encoded {
+        Ok(_) => row.insert("valid", Value::boolean(true)),
+        Err(e) => {
+            let diagnostic = e.to_diagnostic();
+            row.insert("valid", Value::boolean(false));
+            row.insert("error", Value::string(diagnostic.message));
+
+            if let Some(detail) = diagnostic.labels.into_iter().find_map(|l| l.message) {
+                row.insert("detail", Value::string(detail));
             }
         }
-        Bson::Document(d) => shell_encode_document(&mut out, d, span)?,
-        _ => {
-            return Err(ShellError::labeled_error(
-                format!("All top level values must be Documents, got {:?}", bson),
-                "requires BSON-compatible document",
-                span,
-            ))
-        }
     }
-    Ok(out)
+
+    row.into_tagged_value()
 }
 
-fn to_bson(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let name_span = args.name_span();
-    let out = args.input;
+fn to_bson(
+    args: ToBSONArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_span = name;
 
-    Ok(out
+    Ok(input
         .values
-        .map(
-            move |a| match bson_value_to_bytes(value_to_bson_value(&a), name_span) {
-                Ok(x) => ReturnSuccess::value(Value::Binary(x).simple_spanned(name_span)),
-                _ => Err(ShellError::labeled_error_with_secondary(
-                    "Expected an object with BSON-compatible structure from pipeline",
-                    "requires BSON-compatible input: Must be Array or Object",
-                    name_span,
-                    format!("{} originates from here", a.item.type_name()),
-                    a.span(),
-                )),
-            },
-        )
+        .map(move |a| {
+            if args.validate {
+                let encoded = value_to_bson_value(&a, args)
+                    .and_then(|bson| bson_value_to_bytes(bson, name_span));
+                return futures::stream::iter(vec![ReturnSuccess::value(validation_row(
+                    encoded,
+                    a.tag(),
+                ))])
+                .boxed();
+            }
+
+            let bson = match value_to_bson_value(&a, args) {
+                Ok(bson @ Bson::Array(_)) | Ok(bson @ Bson::Document(_)) => bson,
+                Ok(_) => {
+                    return futures::stream::iter(vec![Err(ShellError::type_mismatch(
+                        "BSON-compatible input (an array or object)",
+                        a.tagged_type_name(),
+                        name_span,
+                    ))])
+                    .boxed()
+                }
+                Err(e) => return futures::stream::iter(vec![Err(e)]).boxed(),
+            };
+
+            // With --per-document, a list of documents is emitted one document
+            // at a time so that nothing downstream needs the whole list at once
+            if args.per_document {
+                futures::stream::iter(bson_value_to_chunks(bson, name_span).map(move |chunk| {
+                    chunk.map(|x| ReturnSuccess::Value(Value::Binary(x).simple_spanned(name_span)))
+                }))
+                .boxed()
+            } else {
+                let encoded = bson_value_to_bytes(bson, name_span)
+                    .map(|x| ReturnSuccess::Value(Value::Binary(x).simple_spanned(name_span)));
+
+                futures::stream::iter(vec![encoded]).boxed()
+            }
+        })
+        .flatten()
         .to_output_stream())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{date_to_bson, ToBSON, ToBSONArgs};
+    use crate::commands::testing::run_command;
+    use crate::object::{TaggedDictBuilder, Value};
+    use crate::prelude::*;
+    use bson::Bson;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn keeps_dates_finer_than_a_millisecond_only_when_asked() {
+        let date = Utc.timestamp(1_704_164_645, 123_456_789);
+        let args = ToBSONArgs {
+            decimal128: false,
+            utc_aware: false,
+            validate: false,
+            per_document: false,
+        };
+
+        assert_eq!(
+            date_to_bson(&date, args),
+            Bson::UtcDatetime(Utc.timestamp_millis(1_704_164_645_123))
+        );
+
+        let args = ToBSONArgs {
+            utc_aware: true,
+            ..args
+        };
+
+        match date_to_bson(&date, args) {
+            Bson::Document(doc) => assert_eq!(
+                doc.get_str("$date").ok(),
+                Some("2024-01-02T03:04:05.123456789Z")
+            ),
+            other => panic!("expected an extended JSON date, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_reports_each_row_instead_of_encoding_it() {
+        let mut valid = TaggedDictBuilder::new(Tag::unknown());
+        valid.insert("name", Value::string("nu"));
+
+        let mut invalid = TaggedDictBuilder::new(Tag::unknown());
+        invalid.insert(
+            "run",
+            Value::Block(value::Block::new(
+                vec![],
+                Text::from(String::new()),
+                Span::unknown(),
+            )),
+        );
+
+        let input = vec![valid.into_tagged_value(), invalid.into_tagged_value()];
+        let returned = run_command(ToBSON, &["validate"], input, &CommandRegistry::empty());
+
+        let valid: Vec<Value> = returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(row)) => row
+                    .get_data_by_path(row.tag(), "valid")
+                    .map(|valid| valid.item.clone())
+                    .expect("row without valid"),
+                other => panic!("expected a row, got {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(valid, vec![Value::boolean(true), Value::boolean(false)]);
+    }
+
+    #[test]
+    fn encodes_empty_objects_and_empty_lists() {
+        let input = vec![
+            TaggedDictBuilder::new(Tag::unknown()).into_tagged_value(),
+            Value::List(vec![]).tagged_unknown(),
+        ];
+        let returned = run_command(ToBSON, &[], input, &CommandRegistry::empty());
+
+        let encoded: Vec<Value> = returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(bytes)) => bytes.item,
+                other => panic!("expected encoded bytes, got {:?}", other),
+            })
+            .collect();
+
+        // An empty document is just its length and the closing nul
+        assert_eq!(
+            encoded,
+            vec![Value::Binary(vec![5, 0, 0, 0, 0]), Value::Binary(vec![])]
+        );
+    }
+
+    #[test]
+    fn per_document_chunks_join_up_to_the_whole_encoding() {
+        let document = |name: &str| {
+            let mut row = TaggedDictBuilder::new(Tag::unknown());
+            row.insert("name", Value::string(name));
+            row.into_tagged_value()
+        };
+        let input = || {
+            vec![
+                Value::List(vec![document("nu"), document("rust"), document("bson")])
+                    .tagged_unknown(),
+            ]
+        };
+
+        let bytes = |returned: Vec<ReturnValue>| -> Vec<Vec<u8>> {
+            returned
+                .into_iter()
+                .map(|returned| match returned {
+                    Ok(ReturnSuccess::Value(Tagged {
+                        item: Value::Binary(bytes),
+                        ..
+                    })) => bytes,
+                    other => panic!("expected encoded bytes, got {:?}", other),
+                })
+                .collect()
+        };
+
+        let whole = bytes(run_command(ToBSON, &[], input(), &CommandRegistry::empty()));
+        let chunks = bytes(run_command(
+            ToBSON,
+            &["per-document"],
+            input(),
+            &CommandRegistry::empty(),
+        ));
+
+        assert_eq!(whole.len(), 1);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.concat(), whole[0]);
+    }
+
+    #[test]
+    fn reports_list_items_that_are_not_documents() {
+        let input = || vec![Value::List(vec![Value::int(1).tagged_unknown()]).tagged_unknown()];
+
+        for args in &[&[][..], &["per-document"][..]] {
+            let returned = run_command(ToBSON, args, input(), &CommandRegistry::empty());
+
+            match &returned[..] {
+                [Err(e)] => assert!(format!("{:?}", e).contains("must be Documents")),
+                other => panic!("expected an error, got {:?}", other),
+            }
+        }
+    }
+}
@@ -1,13 +1,21 @@
+use crate::commands::to_json::value_to_json_string;
 use crate::commands::WholeStreamCommand;
+use crate::object::base::column_union;
 use crate::object::{Primitive, Value};
 use crate::prelude::*;
-use csv::WriterBuilder;
+use chrono::format::{Item, StrftimeItems};
+use csv::{QuoteStyle, WriterBuilder};
 
 pub struct ToCSV;
 
 #[derive(Deserialize)]
 pub struct ToCSVArgs {
     headerless: bool,
+    #[serde(rename(deserialize = "quote-style"))]
+    quote_style: Option<Tagged<String>>,
+    nested: Option<Tagged<String>>,
+    #[serde(rename(deserialize = "date-format"))]
+    date_format: Option<Tagged<String>>,
 }
 
 impl WholeStreamCommand for ToCSV {
@@ -18,6 +26,9 @@ impl WholeStreamCommand for ToCSV {
     fn signature(&self) -> Signature {
         Signature::build("to-csv")
             .switch("headerless")
+            .named("quote-style", SyntaxType::Any)
+            .named("nested", SyntaxType::Any)
+            .named("date-format", SyntaxType::Any)
     }
 
     fn usage(&self) -> &str {
@@ -47,9 +58,20 @@ pub fn value_to_csv_value(v: &Value) -> Value {
     }
 }
 
-fn to_string_helper(v: &Value) -> Result<String, Box<dyn std::error::Error>> {
+fn to_string_helper(
+    v: &Value,
+    nested_json: bool,
+    date_format: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
     match v {
-        Value::Primitive(Primitive::Date(d)) => Ok(d.to_string()),
+        Value::List(_) | Value::Object(_) if nested_json => {
+            Ok(value_to_json_string(&v.clone().tagged_unknown(), false)?)
+        }
+        // Dates are written as RFC 3339 so they can be read back unambiguously
+        Value::Primitive(Primitive::Date(d)) => match date_format {
+            Some(format) => Ok(d.format(format).to_string()),
+            None => Ok(d.to_rfc3339()),
+        },
         Value::Primitive(Primitive::Bytes(b)) => Ok(format!("{}", b)),
         Value::Primitive(Primitive::Boolean(_)) => Ok(v.as_string()?),
         Value::List(_) => return Ok(String::from("[list list]")),
@@ -59,16 +81,109 @@ fn to_string_helper(v: &Value) -> Result<String, Box<dyn std::error::Error>> {
     }
 }
 
-pub fn to_string(v: &Value) -> Result<String, Box<dyn std::error::Error>> {
+fn quote_style_from(style: Option<Tagged<String>>) -> Result<QuoteStyle, ShellError> {
+    match style {
+        None => Ok(QuoteStyle::Necessary),
+        Some(style) => match style.item.as_str() {
+            "necessary" => Ok(QuoteStyle::Necessary),
+            "always" => Ok(QuoteStyle::Always),
+            "never" => Ok(QuoteStyle::Never),
+            _ => Err(ShellError::labeled_error(
+                "Unknown quote style",
+                "expected necessary, always, or never",
+                style.span(),
+            )),
+        },
+    }
+}
+
+// A strftime format is checked once up front, so a bad one is reported against
+// the flag instead of failing on every date
+fn date_format_from(format: Option<Tagged<String>>) -> Result<Option<String>, ShellError> {
+    match format {
+        None => Ok(None),
+        Some(format) => {
+            if StrftimeItems::new(&format.item).any(|item| item == Item::Error) {
+                Err(ShellError::labeled_error(
+                    "Could not use the date format",
+                    "expected a format such as %Y-%m-%d",
+                    format.span(),
+                ))
+            } else {
+                Ok(Some(format.item))
+            }
+        }
+    }
+}
+
+// Nested lists and objects are written as "[list list]" and "[object]" unless
+// asked for as JSON, which keeps them recoverable with from-json
+fn nested_json_from(nested: Option<Tagged<String>>) -> Result<bool, ShellError> {
+    match nested {
+        None => Ok(false),
+        Some(nested) => match nested.item.as_str() {
+            "string" => Ok(false),
+            "json" => Ok(true),
+            _ => Err(ShellError::labeled_error(
+                "Unknown nested style",
+                "expected string or json",
+                nested.span(),
+            )),
+        },
+    }
+}
+
+fn needs_quotes(field: &str) -> bool {
+    field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r')
+}
+
+// Without quoting, a field holding the separator, a quote, or a line break can not be
+// told apart from the surrounding structure, so we find it and refuse to write it.
+fn find_unquotable_field(
+    v: &Value,
+    nested_json: bool,
+    date_format: Option<&str>,
+) -> Option<String> {
+    match v {
+        Value::Object(o) => {
+            for (k, v) in o.entries.iter() {
+                if needs_quotes(k) {
+                    return Some(k.clone());
+                }
+
+                if let Ok(field) = to_string_helper(&v, nested_json, date_format) {
+                    if needs_quotes(&field) {
+                        return Some(field);
+                    }
+                }
+            }
+
+            None
+        }
+        _ => match to_string_helper(v, nested_json, date_format) {
+            Ok(ref field) if needs_quotes(field) => Some(field.clone()),
+            _ => None,
+        },
+    }
+}
+
+pub fn to_string(
+    v: &Value,
+    quote_style: QuoteStyle,
+    nested_json: bool,
+    date_format: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
     match v {
         Value::Object(o) => {
-            let mut wtr = WriterBuilder::new().from_writer(vec![]);
+            let mut wtr = WriterBuilder::new()
+                .quote_style(quote_style)
+                .from_writer(vec![]);
             let mut fields: VecDeque<String> = VecDeque::new();
             let mut values: VecDeque<String> = VecDeque::new();
 
             for (k, v) in o.entries.iter() {
                 fields.push_back(k.clone());
-                values.push_back(to_string_helper(&v)?);
+                values.push_back(to_string_helper(&v, nested_json, date_format)?);
             }
 
             wtr.write_record(fields).expect("can not write.");
@@ -76,38 +191,150 @@ pub fn to_string(v: &Value) -> Result<String, Box<dyn std::error::Error>> {
 
             return Ok(String::from_utf8(wtr.into_inner()?)?);
         }
-        _ => return to_string_helper(&v),
+        _ => return to_string_helper(&v, nested_json, date_format),
     }
 }
 
+// Each chunk ends in exactly one newline, so a saved file ends in a single line
+// break. The header is split off at its own newline, which keeps any line
+// breaks quoted inside the values.
+pub(crate) fn csv_chunk(text: String, headerless: bool) -> String {
+    let text = if headerless {
+        text.splitn(2, '\n').nth(1).unwrap_or("").to_string()
+    } else {
+        text
+    };
+
+    if text.is_empty() {
+        return text;
+    }
+
+    format!("{}\n", text.trim_end_matches('\n'))
+}
+
+// Rows can hold different columns, so they are all written under the union of
+// their columns and a column a row doesn't have is left empty
+fn rows_to_csv(
+    rows: &[Value],
+    columns: &[String],
+    quote_style: QuoteStyle,
+    nested_json: bool,
+    date_format: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut wtr = WriterBuilder::new()
+        .quote_style(quote_style)
+        .from_writer(vec![]);
+
+    wtr.write_record(columns)?;
+
+    for row in rows {
+        let mut record = vec![];
+
+        for column in columns {
+            record.push(match row {
+                Value::Object(o) => match o.entries.get(column) {
+                    Some(v) => to_string_helper(&v.item, nested_json, date_format)?,
+                    None => String::new(),
+                },
+                _ => return Err("Bad input".into()),
+            });
+        }
+
+        wtr.write_record(record)?;
+    }
+
+    Ok(String::from_utf8(wtr.into_inner()?)?)
+}
+
 fn to_csv(
-    ToCSVArgs { headerless }: ToCSVArgs,
+    ToCSVArgs {
+        headerless,
+        quote_style,
+        nested,
+        date_format,
+    }: ToCSVArgs,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_span = name;
-    let out = input;
-
-    Ok(out
-        .values
-        .map(move |a| match to_string(&value_to_csv_value(&a.item)) {
-            Ok(x) => {
-                let converted = if headerless {
-                    x.lines().skip(1).collect()
-                } else {
-                    x
+    let quote_style = quote_style_from(quote_style)?;
+    let nested_json = nested_json_from(nested)?;
+    let date_format = date_format_from(date_format)?;
+
+    let stream = async_stream_block! {
+        let rows: Vec<Tagged<Value>> = input.values.collect().await;
+        let date_format = date_format.as_ref().map(String::as_str);
+        let columns = column_union(&rows);
+        let mut values = vec![];
+
+        for row in rows.iter() {
+            let value = value_to_csv_value(&row.item);
+
+            if let QuoteStyle::Never = quote_style {
+                if let Some(field) = find_unquotable_field(&value, nested_json, date_format) {
+                    let error = ShellError::labeled_error_with_secondary(
+                        format!("Can not write {:?} to CSV without quotes", field),
+                        "quote style 'never' can not represent this field",
+                        name_span,
+                        "value originates from here",
+                        row.span(),
+                    );
+                    yield Err(error);
+                    return;
+                }
+            }
+
+            let fits = match (&value, columns.is_empty()) {
+                (Value::Object(_), false) | (_, true) => {
+                    to_string(&value, quote_style, nested_json, date_format).is_ok()
+                }
+                _ => false,
+            };
+
+            if !fits {
+                let error = ShellError::labeled_error_with_secondary(
+                    "Expected an object with CSV-compatible structure from pipeline",
+                    "requires CSV-compatible input",
+                    name_span,
+                    format!("{} originates from here", row.item.type_name()),
+                    row.span(),
+                );
+                yield Err(error);
+                return;
+            }
+
+            values.push(value);
+        }
+
+        // Rows without any columns are plain values, written one per chunk
+        if columns.is_empty() {
+            for value in values {
+                let x = match to_string(&value, quote_style, nested_json, date_format).ok() {
+                    Some(x) => x,
+                    None => continue,
                 };
 
-                ReturnSuccess::value(
-                    Value::Primitive(Primitive::String(converted)).simple_spanned(name_span),
-                )
+                yield ReturnSuccess::value(
+                    Value::Primitive(Primitive::String(csv_chunk(x, headerless)))
+                        .simple_spanned(name_span),
+                );
             }
-            _ => Err(ShellError::labeled_error_with_secondary(
+            return;
+        }
+
+        let written = rows_to_csv(&values, &columns, quote_style, nested_json, date_format).ok();
+
+        match written {
+            Some(x) => yield ReturnSuccess::value(
+                Value::Primitive(Primitive::String(csv_chunk(x, headerless)))
+                    .simple_spanned(name_span),
+            ),
+            None => yield Err(ShellError::labeled_error(
                 "Expected an object with CSV-compatible structure from pipeline",
                 "requires CSV-compatible input",
                 name_span,
-                format!("{} originates from here", a.item.type_name()),
-                a.span(),
             )),
-        })
-        .to_output_stream())
+        }
+    };
+
+    Ok(stream.to_output_stream())
 }
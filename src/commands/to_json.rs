@@ -1,9 +1,21 @@
 use crate::commands::WholeStreamCommand;
-use crate::object::{Primitive, Value};
+use crate::errors::CoerceInto;
+use crate::object::{Dictionary, Primitive, Value};
 use crate::prelude::*;
+use indexmap::IndexMap;
 
 pub struct ToJSON;
 
+#[derive(Deserialize)]
+pub struct ToJSONArgs {
+    columns: Option<Tagged<String>>,
+    json5: bool,
+    array: bool,
+    #[serde(rename(deserialize = "as-object"))]
+    as_object: Option<Tagged<String>>,
+    strict: bool,
+}
+
 impl WholeStreamCommand for ToJSON {
     fn name(&self) -> &str {
         "to-json"
@@ -11,6 +23,11 @@ impl WholeStreamCommand for ToJSON {
 
     fn signature(&self) -> Signature {
         Signature::build("to-json")
+            .named("columns", SyntaxType::Any)
+            .switch("json5")
+            .switch("array")
+            .named("as-object", SyntaxType::Any)
+            .switch("strict")
     }
 
     fn usage(&self) -> &str {
@@ -22,73 +39,298 @@ impl WholeStreamCommand for ToJSON {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        to_json(args, registry)
+        args.process(registry, to_json)?.run()
     }
 }
 
-pub fn value_to_json_value(v: &Value) -> serde_json::Value {
-    match v {
-        Value::Primitive(Primitive::Boolean(b)) => serde_json::Value::Bool(*b),
-        Value::Primitive(Primitive::Bytes(b)) => serde_json::Value::Number(
-            serde_json::Number::from(b.to_u64().expect("What about really big numbers")),
-        ),
-        Value::Primitive(Primitive::Date(d)) => serde_json::Value::String(d.to_string()),
-        Value::Primitive(Primitive::EndOfStream) => serde_json::Value::Null,
-        Value::Primitive(Primitive::BeginningOfStream) => serde_json::Value::Null,
+fn quoted(s: &str) -> String {
+    serde_json::Value::String(s.to_string()).to_string()
+}
+
+// JSON5 lets object keys that are plain identifiers go unquoted; everything
+// else is written exactly as strict JSON would write it
+fn key_to_json_string(key: &str, json5: bool) -> String {
+    if json5 && is_identifier(key) {
+        key.to_string()
+    } else {
+        quoted(key)
+    }
+}
+
+fn entries_to_json_string<'a>(
+    entries: impl Iterator<Item = (&'a String, String)>,
+    json5: bool,
+) -> String {
+    let entries: Vec<String> = entries
+        .map(|(key, value)| format!("{}:{}", key_to_json_string(key, json5), value))
+        .collect();
+
+    format!("{{{}}}", entries.join(","))
+}
+
+// The text is built by hand rather than through serde_json::Value, which
+// can't hold an integer wider than a u64 without losing digits
+pub fn value_to_json_string(v: &Tagged<Value>, json5: bool) -> Result<String, ShellError> {
+    Ok(match v.item() {
+        Value::Primitive(Primitive::Boolean(b)) => b.to_string(),
+        Value::Primitive(Primitive::Bytes(b)) => b.to_string(),
+        Value::Primitive(Primitive::Date(d)) => quoted(&d.to_string()),
+        Value::Primitive(Primitive::EndOfStream) => "null".to_string(),
+        Value::Primitive(Primitive::BeginningOfStream) => "null".to_string(),
         Value::Primitive(Primitive::Decimal(f)) => serde_json::Value::Number(
             serde_json::Number::from_f64(
-                f.to_f64().expect("TODO: What about really big decimals?"),
+                (*f).tagged(v.tag)
+                    .coerce_into("converting to JSON number")?,
             )
-            .unwrap(),
-        ),
-        Value::Primitive(Primitive::Int(i)) => {
-            serde_json::Value::Number(serde_json::Number::from(*i))
-        }
-        Value::Primitive(Primitive::Nothing) => serde_json::Value::Null,
-        Value::Primitive(Primitive::String(s)) => serde_json::Value::String(s.clone()),
-        Value::Primitive(Primitive::Path(s)) => serde_json::Value::String(s.display().to_string()),
+            .expect("coerced decimals are always finite"),
+        )
+        .to_string(),
+        Value::Primitive(Primitive::Int(i)) => i.to_string(),
+        Value::Primitive(Primitive::BigInt(i)) => i.to_string(),
+        Value::Primitive(Primitive::Nothing) => "null".to_string(),
+        Value::Primitive(Primitive::String(s)) => quoted(s),
+        Value::Primitive(Primitive::Path(s)) => quoted(&s.display().to_string()),
 
-        Value::List(l) => {
-            serde_json::Value::Array(l.iter().map(|x| value_to_json_value(x)).collect())
+        Value::List(l) => format!(
+            "[{}]",
+            l.iter()
+                .map(|x| value_to_json_string(x, json5))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(",")
+        ),
+        Value::Block(_) => {
+            return Err(ShellError::labeled_error(
+                "Blocks can not be converted to JSON",
+                "block originates from here",
+                v.span(),
+            ))
         }
-        Value::Block(_) => serde_json::Value::Null,
-        Value::Binary(b) => serde_json::Value::Array(
+        Value::Binary(b) => format!(
+            "[{}]",
             b.iter()
                 .map(|x| {
-                    serde_json::Value::Number(serde_json::Number::from_f64(*x as f64).unwrap())
+                    serde_json::Value::Number(serde_json::Number::from_f64(f64::from(*x)).unwrap())
+                        .to_string()
                 })
-                .collect(),
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        // Keys are written in the order the row holds its columns
+        Value::Object(o) => entries_to_json_string(
+            o.entries
+                .iter()
+                .map(|(k, v)| Ok((k, value_to_json_string(v, json5)?)))
+                .collect::<Result<Vec<_>, ShellError>>()?
+                .into_iter(),
+            json5,
         ),
-        Value::Object(o) => {
-            let mut m = serde_json::Map::new();
-            for (k, v) in o.entries.iter() {
-                m.insert(k.clone(), value_to_json_value(v));
+    })
+}
+
+// Only the listed columns are written, in the order given. Missing ones are skipped.
+fn columns_to_json_string(
+    o: &Dictionary,
+    columns: &[String],
+    json5: bool,
+) -> Result<String, ShellError> {
+    let mut entries = vec![];
+    for column in columns {
+        if let Some(v) = o.entries.get(column) {
+            entries.push((column, value_to_json_string(v, json5)?));
+        }
+    }
+    Ok(entries_to_json_string(entries.into_iter(), json5))
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == '$' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        }
+        _ => false,
+    }
+}
+
+// Each row gives one entry, with its key read from one column and its value
+// from another. A repeated key keeps the last value, or fails when strict.
+fn rows_to_json_object(
+    rows: &[Tagged<Value>],
+    key_column: &str,
+    value_column: &str,
+    strict: bool,
+    json5: bool,
+    span: Span,
+) -> Result<String, ShellError> {
+    let mut m = IndexMap::new();
+
+    for row in rows {
+        let (key, value) = match (
+            row.get_data_by_path(row.tag(), key_column),
+            row.get_data_by_path(row.tag(), value_column),
+        ) {
+            (Some(key), Some(value)) => (key, value),
+            _ => {
+                return Err(ShellError::labeled_error_with_secondary(
+                    "Could not find the key and value columns",
+                    format!("requires '{}' and '{}'", key_column, value_column),
+                    span,
+                    "row originates from here",
+                    row.span(),
+                ))
             }
-            serde_json::Value::Object(m)
+        };
+
+        let key = key.item.as_string().map_err(|_| {
+            ShellError::labeled_error_with_secondary(
+                "Expected text for the key",
+                format!("'{}' is {}", key_column, key.item.type_name()),
+                span,
+                "row originates from here",
+                row.span(),
+            )
+        })?;
+
+        if strict && m.contains_key(&key) {
+            return Err(ShellError::labeled_error_with_secondary(
+                format!("Key '{}' appears more than once", key),
+                "duplicate keys are not allowed with --strict",
+                span,
+                "repeated here",
+                row.span(),
+            ));
         }
+
+        let value = value_to_json_string(&value.item.clone().tagged(value.tag), json5)?;
+        m.insert(key, value);
     }
+
+    Ok(entries_to_json_string(
+        m.iter().map(|(k, v)| (k, v.clone())),
+        json5,
+    ))
 }
 
-fn to_json(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let name_span = args.name_span();
-    let out = args.input;
-
-    Ok(out
-        .values
-        .map(
-            move |a| match serde_json::to_string(&value_to_json_value(&a)) {
-                Ok(x) => ReturnSuccess::value(
-                    Value::Primitive(Primitive::String(x)).simple_spanned(name_span),
-                ),
-                _ => Err(ShellError::labeled_error_with_secondary(
-                    "Expected an object with JSON-compatible structure from pipeline",
-                    "requires JSON-compatible input",
-                    name_span,
-                    format!("{} originates from here", a.item.type_name()),
-                    a.span(),
-                )),
-            },
-        )
-        .to_output_stream())
+fn row_to_json_string(
+    a: &Tagged<Value>,
+    columns: &Option<Vec<String>>,
+    json5: bool,
+) -> Result<String, ShellError> {
+    match (&a.item, columns) {
+        (Value::Object(o), Some(columns)) => columns_to_json_string(o, columns, json5),
+        _ => value_to_json_string(a, json5),
+    }
+}
+
+fn to_json(
+    ToJSONArgs {
+        columns,
+        json5,
+        array,
+        as_object,
+        strict,
+    }: ToJSONArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_span = name;
+    let columns: Option<Vec<String>> = columns.map(|c| {
+        c.item
+            .split(',')
+            .map(|column| column.trim().to_string())
+            .collect()
+    });
+
+    let text = move |s: String| ReturnSuccess::value(Value::string(s).simple_spanned(name_span));
+
+    if let Some(Tagged { item, tag }) = as_object {
+        let columns: Vec<&str> = item.split(',').map(str::trim).collect();
+        let (key_column, value_column) = match columns[..] {
+            [key, value] => (key.to_string(), value.to_string()),
+            _ => {
+                return Err(ShellError::labeled_error(
+                    "Expected a key column and a value column",
+                    "expected eg) --as-object name,value",
+                    tag.span,
+                ))
+            }
+        };
+
+        // The whole stream becomes a single object, so it is collected first
+        let stream = async_stream_block! {
+            let rows: Vec<Tagged<Value>> = input.values.collect().await;
+
+            let json =
+                rows_to_json_object(&rows, &key_column, &value_column, strict, json5, tag.span);
+
+            yield json.and_then(text);
+        };
+
+        return Ok(stream.to_output_stream());
+    }
+
+    if !array {
+        return Ok(input
+            .values
+            .map(move |a| row_to_json_string(&a, &columns, json5).and_then(text))
+            .to_output_stream());
+    }
+
+    // Each row is written as soon as it arrives, so the whole array is never
+    // held in memory. Joined together, the chunks read as a single JSON array.
+    let open = futures::stream::once(futures::future::ready(text("[".to_string())));
+    let rows = input.values.enumerate().map(move |(idx, a)| {
+        row_to_json_string(&a, &columns, json5).and_then(|json| match idx {
+            0 => text(json),
+            _ => text(format!(",{}", json)),
+        })
+    });
+    let close = futures::stream::once(futures::future::ready(text("]".to_string())));
+
+    Ok(open.chain(rows).chain(close).to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToJSON;
+    use crate::commands::testing::run_command;
+    use crate::object::{Primitive, TaggedDictBuilder, Value};
+    use crate::prelude::*;
+
+    fn row(n: i64) -> Tagged<Value> {
+        let mut row = TaggedDictBuilder::new(Tag::unknown());
+        row.insert("n", Value::int(n));
+        row.into_tagged_value()
+    }
+
+    fn joined(returned: Vec<ReturnValue>) -> String {
+        returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(Tagged {
+                    item: Value::Primitive(Primitive::String(s)),
+                    ..
+                })) => s,
+                other => panic!("expected text, got {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn writes_json_text_for_each_row() {
+        let returned = run_command(ToJSON, &[], vec![row(1), row(2)], &CommandRegistry::empty());
+
+        assert_eq!(joined(returned), r#"{"n":1}{"n":2}"#);
+    }
+
+    #[test]
+    fn array_chunks_join_into_one_json_array() {
+        let registry = CommandRegistry::empty();
+
+        let returned = run_command(ToJSON, &["array"], vec![row(1), row(2)], &registry);
+        assert_eq!(joined(returned), r#"[{"n":1},{"n":2}]"#);
+
+        let returned = run_command(ToJSON, &["array"], vec![], &registry);
+        assert_eq!(joined(returned), "[]");
+    }
 }
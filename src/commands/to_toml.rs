@@ -44,6 +44,8 @@ pub fn value_to_toml_value(v: &Value) -> Result<toml::Value, ShellError> {
             toml::Value::Float(ranged(f.to_f64(), "f64", f.tagged_unknown())?)
         }
         Value::Primitive(Primitive::Int(i)) => toml::Value::Integer(*i),
+        // TOML integers stop at 64 bits, so wider ones are kept as text
+        Value::Primitive(Primitive::BigInt(i)) => toml::Value::String(i.to_string()),
         Value::Primitive(Primitive::Nothing) => toml::Value::String("<Nothing>".to_string()),
         Value::Primitive(Primitive::String(s)) => toml::Value::String(s.clone()),
         Value::Primitive(Primitive::Path(s)) => toml::Value::String(s.display().to_string()),
@@ -1,4 +1,6 @@
+use crate::commands::to_csv::csv_chunk;
 use crate::commands::WholeStreamCommand;
+use crate::object::base::column_union;
 use crate::object::{Primitive, Value};
 use crate::prelude::*;
 use csv::WriterBuilder;
@@ -80,34 +82,96 @@ pub fn to_string(v: &Value) -> Result<String, Box<dyn std::error::Error>> {
     }
 }
 
+// Rows can hold different columns, so they are all written under the union of
+// their columns and a column a row doesn't have is left empty
+fn rows_to_tsv(rows: &[Value], columns: &[String]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut wtr = WriterBuilder::new().delimiter(b'\t').from_writer(vec![]);
+
+    wtr.write_record(columns)?;
+
+    for row in rows {
+        let mut record = vec![];
+
+        for column in columns {
+            record.push(match row {
+                Value::Object(o) => match o.entries.get(column) {
+                    Some(v) => to_string_helper(&v.item)?,
+                    None => String::new(),
+                },
+                _ => return Err("Bad input".into()),
+            });
+        }
+
+        wtr.write_record(record)?;
+    }
+
+    Ok(String::from_utf8(wtr.into_inner()?)?)
+}
+
 fn to_tsv(
     ToTSVArgs { headerless }: ToTSVArgs,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_span = name;
-    let out = input;
-
-    Ok(out
-        .values
-        .map(move |a| match to_string(&value_to_tsv_value(&a.item)) {
-            Ok(x) => {
-                let converted = if headerless {
-                    x.lines().skip(1).collect()
-                } else {
-                    x
+
+    let stream = async_stream_block! {
+        let rows: Vec<Tagged<Value>> = input.values.collect().await;
+        let columns = column_union(&rows);
+        let mut values = vec![];
+
+        for row in rows.iter() {
+            let value = value_to_tsv_value(&row.item);
+
+            let fits = match (&value, columns.is_empty()) {
+                (Value::Object(_), false) | (_, true) => to_string(&value).is_ok(),
+                _ => false,
+            };
+
+            if !fits {
+                let error = ShellError::labeled_error_with_secondary(
+                    "Expected an object with TSV-compatible structure from pipeline",
+                    "requires TSV-compatible input",
+                    name_span,
+                    format!("{} originates from here", row.item.type_name()),
+                    row.span(),
+                );
+                yield Err(error);
+                return;
+            }
+
+            values.push(value);
+        }
+
+        // Rows without any columns are plain values, written one per chunk
+        if columns.is_empty() {
+            for value in values {
+                let x = match to_string(&value).ok() {
+                    Some(x) => x,
+                    None => continue,
                 };
 
-                ReturnSuccess::value(
-                    Value::Primitive(Primitive::String(converted)).simple_spanned(name_span),
-                )
+                yield ReturnSuccess::value(
+                    Value::Primitive(Primitive::String(csv_chunk(x, headerless)))
+                        .simple_spanned(name_span),
+                );
             }
-            _ => Err(ShellError::labeled_error_with_secondary(
+            return;
+        }
+
+        let written = rows_to_tsv(&values, &columns).ok();
+
+        match written {
+            Some(x) => yield ReturnSuccess::value(
+                Value::Primitive(Primitive::String(csv_chunk(x, headerless)))
+                    .simple_spanned(name_span),
+            ),
+            None => yield Err(ShellError::labeled_error(
                 "Expected an object with TSV-compatible structure from pipeline",
                 "requires TSV-compatible input",
                 name_span,
-                format!("{} originates from here", a.item.type_name()),
-                a.span(),
             )),
-        })
-        .to_output_stream())
+        }
+    };
+
+    Ok(stream.to_output_stream())
 }
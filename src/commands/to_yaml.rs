@@ -4,13 +4,18 @@ use crate::prelude::*;
 
 pub struct ToYAML;
 
+#[derive(Deserialize)]
+pub struct ToYAMLArgs {
+    flow: bool,
+}
+
 impl WholeStreamCommand for ToYAML {
     fn name(&self) -> &str {
         "to-yaml"
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("to-yaml")
+        Signature::build("to-yaml").switch("flow")
     }
 
     fn usage(&self) -> &str {
@@ -22,7 +27,7 @@ impl WholeStreamCommand for ToYAML {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        to_yaml(args, registry)
+        args.process(registry, to_yaml)?.run()
     }
 }
 
@@ -41,6 +46,8 @@ pub fn value_to_yaml_value(v: &Value) -> serde_yaml::Value {
         Value::Primitive(Primitive::Int(i)) => {
             serde_yaml::Value::Number(serde_yaml::Number::from(*i))
         }
+        // YAML numbers stop at 64 bits, so wider integers are kept as text
+        Value::Primitive(Primitive::BigInt(i)) => serde_yaml::Value::String(i.to_string()),
         Value::Primitive(Primitive::Nothing) => serde_yaml::Value::Null,
         Value::Primitive(Primitive::String(s)) => serde_yaml::Value::String(s.clone()),
         Value::Primitive(Primitive::Path(s)) => serde_yaml::Value::String(s.display().to_string()),
@@ -64,14 +71,65 @@ pub fn value_to_yaml_value(v: &Value) -> serde_yaml::Value {
     }
 }
 
-fn to_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let name_span = args.name_span();
-    let out = args.input;
-    Ok(out
+fn flow_scalar(v: &serde_yaml::Value) -> String {
+    match v {
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::String(s) => {
+            let plain = serde_yaml::to_string(s).unwrap_or_default();
+            let plain = plain.trim_start_matches("---").trim();
+
+            // Flow collections give , [ ] { } a meaning of their own, so
+            // fall back to a double-quoted scalar whenever they show up
+            if plain.is_empty() || plain.contains(|c: char| ",[]{}".contains(c)) {
+                serde_json::to_string(s).unwrap_or_default()
+            } else {
+                plain.to_string()
+            }
+        }
+        other => value_to_flow_yaml(other),
+    }
+}
+
+// serde_yaml only emits block style, so flow style is written by hand
+pub fn value_to_flow_yaml(v: &serde_yaml::Value) -> String {
+    match v {
+        serde_yaml::Value::Sequence(l) => format!(
+            "[{}]",
+            l.iter()
+                .map(|x| value_to_flow_yaml(x))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        serde_yaml::Value::Mapping(m) => format!(
+            "{{{}}}",
+            m.iter()
+                .map(|(k, v)| format!("{}: {}", flow_scalar(k), value_to_flow_yaml(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        scalar => flow_scalar(scalar),
+    }
+}
+
+fn to_yaml(
+    ToYAMLArgs { flow }: ToYAMLArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_span = name;
+    Ok(input
         .values
-        .map(
-            move |a| match serde_yaml::to_string(&value_to_yaml_value(&a)) {
+        .map(move |a| {
+            let yaml_value = value_to_yaml_value(&a);
+
+            let yaml = if flow {
+                Ok(value_to_flow_yaml(&yaml_value))
+            } else {
+                serde_yaml::to_string(&yaml_value)
+            };
+
+            match yaml {
                 Ok(x) => ReturnSuccess::value(
                     Value::Primitive(Primitive::String(x)).simple_spanned(name_span),
                 ),
@@ -82,7 +140,7 @@ fn to_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream
                     format!("{} originates from here", a.item.type_name()),
                     a.span(),
                 )),
-            },
-        )
+            }
+        })
         .to_output_stream())
 }
@@ -0,0 +1,106 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{Primitive, Value};
+use crate::prelude::*;
+use regex::Regex;
+
+pub struct Tokenize;
+
+#[derive(Deserialize)]
+pub struct TokenizeArgs {
+    punctuation: bool,
+    pattern: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Tokenize {
+    fn name(&self) -> &str {
+        "tokenize"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("tokenize")
+            .switch("punctuation")
+            .named("pattern", SyntaxType::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Split text into a row for each word, or for each match of '--pattern'."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, tokenize)?.run()
+    }
+}
+
+enum Splitter {
+    Whitespace,
+    // Words are runs of letters and digits, so "don't" gives "don" and "t"
+    Punctuation,
+    Pattern(Regex),
+}
+
+impl Splitter {
+    fn tokens<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        match self {
+            Splitter::Whitespace => s.split_whitespace().collect(),
+            Splitter::Punctuation => s
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|token| !token.is_empty())
+                .collect(),
+            Splitter::Pattern(regex) => regex
+                .find_iter(s)
+                .map(|found| found.as_str())
+                .filter(|token| !token.is_empty())
+                .collect(),
+        }
+    }
+}
+
+fn tokenize(
+    TokenizeArgs {
+        punctuation,
+        pattern,
+    }: TokenizeArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let splitter = match pattern {
+        Some(pattern) => match Regex::new(&pattern.item) {
+            Ok(regex) => Splitter::Pattern(regex),
+            Err(e) => {
+                return Err(ShellError::labeled_error(
+                    "Could not parse pattern",
+                    format!("{}", e),
+                    pattern.span(),
+                ))
+            }
+        },
+        None if punctuation => Splitter::Punctuation,
+        None => Splitter::Whitespace,
+    };
+
+    let stream = input
+        .values
+        .map(move |v| match v.item {
+            Value::Primitive(Primitive::String(ref s)) => splitter
+                .tokens(s)
+                .into_iter()
+                .map(|token| ReturnSuccess::value(Value::string(token).tagged(v.tag())))
+                .collect::<VecDeque<_>>(),
+            _ => {
+                let mut result = VecDeque::new();
+                result.push_back(Err(ShellError::type_mismatch(
+                    "string input",
+                    v.tagged_type_name(),
+                    name,
+                )));
+                result
+            }
+        })
+        .flatten();
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,41 @@
+use crate::commands::command::RunnablePerItemContext;
+use crate::errors::ShellError;
+use crate::parser::registry::{CommandRegistry, Signature};
+use crate::prelude::*;
+use std::path::PathBuf;
+
+pub struct Touch;
+
+#[derive(Deserialize)]
+pub struct TouchArgs {
+    pub rest: Vec<Tagged<PathBuf>>,
+}
+
+impl PerItemCommand for Touch {
+    fn name(&self) -> &str {
+        "touch"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("touch").rest(SyntaxType::Path)
+    }
+
+    fn usage(&self) -> &str {
+        "Creates files, or updates the modification time of files that already exist."
+    }
+
+    fn run(
+        &self,
+        call_info: &CallInfo,
+        _registry: &CommandRegistry,
+        raw_args: &RawCommandArgs,
+        _input: Tagged<Value>,
+    ) -> Result<OutputStream, ShellError> {
+        call_info.process(&raw_args.shell_manager, touch)?.run()
+    }
+}
+
+fn touch(args: TouchArgs, context: &RunnablePerItemContext) -> Result<OutputStream, ShellError> {
+    let shell_manager = context.shell_manager.clone();
+    shell_manager.touch(args, context)
+}
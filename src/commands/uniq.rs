@@ -0,0 +1,61 @@
+use crate::commands::duplicates::ValueCounts;
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Uniq;
+
+#[derive(Deserialize)]
+pub struct UniqArgs {
+    adjacent: bool,
+}
+
+impl WholeStreamCommand for Uniq {
+    fn name(&self) -> &str {
+        "uniq"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("uniq").switch("adjacent")
+    }
+
+    fn usage(&self) -> &str {
+        "Show each distinct row once, or with '--adjacent' only drop rows that repeat the row before them."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, uniq)?.run()
+    }
+}
+
+// Rows are passed on as they arrive either way. Without --adjacent every
+// distinct row is remembered, so memory grows with the number of distinct
+// rows; with it only the previous row is kept.
+pub fn uniq(
+    UniqArgs { adjacent }: UniqArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+        let mut seen = ValueCounts::default();
+
+        while let Some(row) = values.next().await {
+            if seen.contains(&row.item) {
+                continue;
+            }
+
+            if adjacent {
+                seen.clear();
+            }
+
+            seen.add(row.clone());
+            yield ReturnSuccess::value(row);
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
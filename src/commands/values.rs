@@ -0,0 +1,77 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::Value;
+use crate::prelude::*;
+
+pub struct Values;
+
+#[derive(Deserialize)]
+pub struct ValuesArgs {
+    lists: bool,
+}
+
+impl WholeStreamCommand for Values {
+    fn name(&self) -> &str {
+        "values"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("values").switch("lists")
+    }
+
+    fn usage(&self) -> &str {
+        "Output the values of each row, dropping the column names."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, values)?.run()
+    }
+}
+
+fn row_values(row: Tagged<Value>, lists: bool, name: Span) -> VecDeque<ReturnValue> {
+    let mut result = VecDeque::new();
+    let row_tag = row.tag();
+
+    match row.item {
+        Value::Object(o) => {
+            let entries = o.entries.into_iter().map(|(_, v)| v);
+
+            if lists {
+                result.push_back(ReturnSuccess::value(
+                    Value::List(entries.collect()).tagged(row_tag),
+                ));
+            } else {
+                for v in entries {
+                    result.push_back(ReturnSuccess::value(v));
+                }
+            }
+        }
+        Value::List(l) => {
+            for row in l {
+                result.extend(row_values(row, lists, name));
+            }
+        }
+        item => result.push_back(Err(ShellError::type_mismatch(
+            "a table or object",
+            item.tagged(row_tag).tagged_type_name(),
+            name,
+        ))),
+    }
+
+    result
+}
+
+fn values(
+    ValuesArgs { lists }: ValuesArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    Ok(input
+        .values
+        .map(move |row| row_values(row, lists, name))
+        .flatten()
+        .to_output_stream())
+}
@@ -1,62 +1,86 @@
-use crate::commands::PerItemCommand;
+use crate::commands::predicate::Condition;
+use crate::commands::WholeStreamCommand;
 use crate::errors::ShellError;
-use crate::parser::hir::SyntaxType;
-use crate::parser::registry;
 use crate::prelude::*;
 
 pub struct Where;
 
-impl PerItemCommand for Where {
+#[derive(Deserialize)]
+pub struct WhereArgs {
+    condition: Option<Tagged<Value>>,
+    has: Option<Tagged<String>>,
+    insensitive: bool,
+}
+
+impl WholeStreamCommand for Where {
     fn name(&self) -> &str {
         "where"
     }
 
-    fn signature(&self) -> registry::Signature {
+    fn signature(&self) -> Signature {
         Signature::build("where")
-            .required("condition", SyntaxType::Block)
+            .optional("condition", SyntaxType::Block)
+            .named("has", SyntaxType::Member)
+            .switch("insensitive")
     }
 
     fn usage(&self) -> &str {
-        "Filter table to match the condition."
+        "Filter table to match the condition, eg) where size > 1mb, where name contains test or where size is int. --insensitive needs a quoted condition or one using contains."
     }
 
     fn run(
         &self,
-        call_info: &CallInfo,
-        _registry: &registry::CommandRegistry,
-        _raw_args: &RawCommandArgs,
-        input: Tagged<Value>,
+        args: CommandArgs,
+        registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        let input_clone = input.clone();
-        let condition = call_info.args.expect_nth(0)?;
-        let stream = match condition {
-            Tagged {
-                item: Value::Block(block),
-                ..
-            } => {
-                let result = block.invoke(&input_clone);
-                match result {
-                    Ok(v) => {
-                        if v.is_true() {
-                            VecDeque::from(vec![Ok(ReturnSuccess::Value(input_clone))])
-                        } else {
-                            VecDeque::new()
-                        }
-                    }
-                    Err(e) => {
-                        return Err(e)
-                    }
-                }
-            }
-            Tagged { tag, .. } => {
-                return Err(ShellError::labeled_error(
-                    "Expected a condition",
-                    "where needs a condition",
-                    tag.span,
-                ))
-            }
+        args.process(registry, where_)?.run()
+    }
+}
+
+pub fn where_(
+    WhereArgs {
+        condition,
+        has,
+        insensitive,
+    }: WhereArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let condition = match (condition, insensitive) {
+        (Some(condition), true) => {
+            let span = condition.span();
+            Some(Condition::from_value(condition)?.insensitive(span)?)
+        }
+        (Some(condition), false) => Some(Condition::from_value(condition)?),
+        (None, _) if has.is_some() => None,
+        (None, _) => {
+            return Err(ShellError::labeled_error(
+                "Expected a condition",
+                "where needs a condition or --has",
+                name,
+            ))
+        }
+    };
+
+    let objects = input.values.filter_map(move |item| {
+        // A column counts as present even when its value is empty
+        let has_column = match &has {
+            Some(column) => item.get_data_by_path(item.tag(), &column.item).is_some(),
+            None => true,
         };
 
-        Ok(stream.to_output_stream())
-    }
+        let matched = match &condition {
+            Some(condition) if has_column => condition.test(&item),
+            _ => Ok(has_column),
+        };
+
+        let result = match matched {
+            Ok(true) => Some(ReturnSuccess::value(item)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        };
+
+        futures::future::ready(result)
+    });
+
+    Ok(objects.to_output_stream())
 }
@@ -0,0 +1,97 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::parser::CommandRegistry;
+use crate::prelude::*;
+
+#[derive(Deserialize)]
+struct WindowArgs {
+    size: Tagged<i64>,
+    stride: Option<Tagged<i64>>,
+}
+
+pub struct Window;
+
+impl WholeStreamCommand for Window {
+    fn name(&self) -> &str {
+        "window"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("window")
+            .required("size", SyntaxType::Number)
+            .named("stride", SyntaxType::Number)
+    }
+
+    fn usage(&self) -> &str {
+        "Group rows into lists of the given size, moving forward by the stride (defaults to the size)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, window)?.run()
+    }
+}
+
+fn window(
+    WindowArgs { size, stride }: WindowArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    if size.item <= 0 {
+        return Err(ShellError::labeled_error(
+            "Window size must be positive",
+            "expected a number greater than zero",
+            size.span(),
+        ));
+    }
+
+    let stride = match stride {
+        Some(stride) if stride.item <= 0 => {
+            return Err(ShellError::labeled_error(
+                "Window stride must be positive",
+                "expected a number greater than zero",
+                stride.span(),
+            ))
+        }
+        Some(stride) => stride.item as usize,
+        None => size.item as usize,
+    };
+    let size = size.item as usize;
+
+    let stream = async_stream_block! {
+        let mut values = input.values;
+        let mut window: VecDeque<Tagged<Value>> = VecDeque::with_capacity(size);
+        let mut to_skip = 0;
+        let mut unemitted = 0;
+
+        while let Some(value) = values.next().await {
+            if to_skip > 0 {
+                to_skip -= 1;
+                continue;
+            }
+
+            window.push_back(value);
+            unemitted += 1;
+
+            if window.len() == size {
+                yield ReturnSuccess::value(
+                    Value::List(window.iter().cloned().collect()).simple_spanned(name),
+                );
+                unemitted = 0;
+
+                let to_drop = stride.min(size);
+                window.drain(..to_drop);
+                to_skip = stride - to_drop;
+            }
+        }
+
+        // Rows that never made it into a full window are emitted as a final, shorter one
+        if unemitted > 0 {
+            yield ReturnSuccess::value(Value::List(window.into_iter().collect()).simple_spanned(name));
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,103 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::object::{TaggedDictBuilder, Value};
+use crate::prelude::*;
+
+pub struct Zip;
+
+#[derive(Deserialize)]
+pub struct ZipArgs {
+    other: Tagged<Value>,
+    merge: bool,
+}
+
+impl WholeStreamCommand for Zip {
+    fn name(&self) -> &str {
+        "zip"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("zip")
+            .required("other", SyntaxType::Any)
+            .switch("merge")
+    }
+
+    fn usage(&self) -> &str {
+        "Pair each row with the matching item of a list, stopping at the shorter of the two."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, zip)?.run()
+    }
+}
+
+pub(crate) fn merge_rows(
+    left: Tagged<Value>,
+    right: Tagged<Value>,
+    name: Span,
+) -> Result<Tagged<Value>, ShellError> {
+    match (&left.item, &right.item) {
+        (Value::Object(l), Value::Object(r)) => {
+            let mut merged = TaggedDictBuilder::new(left.tag());
+
+            // Columns from the other side win when both rows have them, but
+            // keep their place in the first row
+            for (k, v) in l.entries.iter() {
+                let v = r.entries.get(k).unwrap_or(v);
+                merged.insert_tagged(k.clone(), v.clone());
+            }
+
+            for (k, v) in r
+                .entries
+                .iter()
+                .filter(|(k, _)| !l.entries.contains_key(*k))
+            {
+                merged.insert_tagged(k.clone(), v.clone());
+            }
+
+            Ok(merged.into_tagged_value())
+        }
+        (Value::Object(_), _) => Err(ShellError::type_mismatch(
+            "a row to merge",
+            right.tagged_type_name(),
+            name,
+        )),
+        _ => Err(ShellError::type_mismatch(
+            "a row to merge",
+            left.tagged_type_name(),
+            name,
+        )),
+    }
+}
+
+fn zip(
+    ZipArgs { other, merge }: ZipArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let others = match other.item {
+        Value::List(list) => list,
+        _ => {
+            return Err(ShellError::type_mismatch(
+                "a list",
+                other.tagged_type_name(),
+                other.span(),
+            ))
+        }
+    };
+
+    Ok(input
+        .values
+        .zip(futures::stream::iter(others))
+        .map(move |(left, right)| {
+            if merge {
+                merge_rows(left, right, name).map(ReturnSuccess::Value)
+            } else {
+                ReturnSuccess::value(Value::List(vec![left, right]).simple_spanned(name))
+            }
+        })
+        .to_output_stream())
+}
@@ -74,7 +74,7 @@ impl CommandRegistry {
 pub struct Context {
     registry: CommandRegistry,
     pub(crate) source_map: SourceMap,
-    host: Arc<Mutex<dyn Host + Send>>,
+    host: Arc<Mutex<dyn Host>>,
     pub(crate) shell_manager: ShellManager,
 }
 
@@ -93,6 +93,22 @@ impl Context {
         })
     }
 
+    // Shares the commands, host and shells of a running command, so pipelines
+    // run through it see and change the same state as the shell around it
+    pub(crate) fn from_parts(
+        registry: CommandRegistry,
+        source_map: SourceMap,
+        host: Arc<Mutex<dyn Host>>,
+        shell_manager: ShellManager,
+    ) -> Context {
+        Context {
+            registry,
+            source_map,
+            host,
+            shell_manager,
+        }
+    }
+
     pub(crate) fn with_host(&mut self, block: impl FnOnce(&mut dyn Host)) {
         let mut host = self.host.lock().unwrap();
 
@@ -74,6 +74,19 @@ impl ShellError {
         .start()
     }
 
+    pub(crate) fn type_mismatch(
+        expected: impl Into<String>,
+        actual: Tagged<impl Into<String>>,
+        command: Span,
+    ) -> ShellError {
+        ProximateShellError::TypeMismatch {
+            expected: expected.into(),
+            actual: actual.map(|a| a.into()),
+            command,
+        }
+        .start()
+    }
+
     pub(crate) fn range_error(
         expected: impl Into<String>,
         actual: Tagged<impl fmt::Debug>,
@@ -243,6 +256,20 @@ impl ShellError {
             } => Diagnostic::new(Severity::Error, "Type Error")
                 .with_label(Label::new_primary(span).with_message(expected)),
 
+            ProximateShellError::TypeMismatch {
+                expected,
+                actual,
+                command,
+            } => Diagnostic::new(
+                Severity::Error,
+                format!("Expected {} from pipeline", expected),
+            )
+            .with_label(Label::new_primary(command).with_message(format!("requires {}", expected)))
+            .with_label(
+                Label::new_secondary(actual.span())
+                    .with_message(format!("{} originates from here", actual.item)),
+            ),
+
             ProximateShellError::RangeError {
                 kind,
                 actual_kind:
@@ -320,6 +347,22 @@ impl ShellError {
         )
     }
 
+    /// A converter's input could not be parsed; `detail` is the parser's own explanation
+    pub fn parse_failure(
+        format: &str,
+        detail: impl std::fmt::Display,
+        span: Span,
+        origin: Span,
+    ) -> ShellError {
+        ShellError::labeled_error_with_secondary(
+            format!("Could not parse as {}", format),
+            format!("input cannot be parsed as {}", format),
+            span,
+            detail.to_string(),
+            origin,
+        )
+    }
+
     pub fn string(title: impl Into<String>) -> ShellError {
         ProximateShellError::String(StringError::new(title.into(), Value::nothing())).start()
     }
@@ -346,6 +389,11 @@ pub enum ProximateShellError {
         expected: String,
         actual: Tagged<Option<String>>,
     },
+    TypeMismatch {
+        expected: String,
+        actual: Tagged<String>,
+        command: Span,
+    },
     MissingProperty {
         subpath: Description,
         expr: Description,
@@ -442,6 +490,7 @@ impl std::fmt::Display for ShellError {
             ProximateShellError::MissingValue { .. } => write!(f, "MissingValue"),
             ProximateShellError::InvalidCommand { .. } => write!(f, "InvalidCommand"),
             ProximateShellError::TypeError { .. } => write!(f, "TypeError"),
+            ProximateShellError::TypeMismatch { .. } => write!(f, "TypeMismatch"),
             ProximateShellError::RangeError { .. } => write!(f, "RangeError"),
             ProximateShellError::SyntaxError { .. } => write!(f, "SyntaxError"),
             ProximateShellError::MissingProperty { .. } => write!(f, "MissingProperty"),
@@ -547,6 +596,35 @@ impl<T> ShellErrorUtils<Tagged<T>> for Option<Tagged<T>> {
     }
 }
 
+pub trait CoerceInto<U> {
+    fn coerce_into(self, operation: impl Into<String>) -> Result<U, ShellError>;
+}
+
+impl CoerceInto<f64> for Tagged<Decimal> {
+    fn coerce_into(self, operation: impl Into<String>) -> Result<f64, ShellError> {
+        let decimal = self.item;
+        let float = ranged(decimal.to_f64(), "f64", self.copy_span(decimal))?;
+
+        // f64 only carries ~15 significant digits, so allow the usual rounding but
+        // refuse anything that drifts further than a billionth of the original value
+        let lossless = match Decimal::from_f64(float) {
+            Some(back) if back == decimal => true,
+            Some(back) => (back - decimal).abs() / decimal.abs() <= Decimal::new(1, 9),
+            None => false,
+        };
+
+        if lossless {
+            Ok(float)
+        } else {
+            Err(ShellError::labeled_error(
+                format!("Could not convert decimal while {}", operation.into()),
+                format!("{} loses precision as f64", decimal),
+                self.span(),
+            ))
+        }
+    }
+}
+
 pub fn ranged<T>(
     input: Option<T>,
     expected: impl Into<String>,
@@ -557,3 +635,36 @@ pub fn ranged<T>(
         None => Err(ShellError::range_error(expected, actual)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CoerceInto;
+    use crate::prelude::*;
+    use std::str::FromStr;
+
+    fn coerce(decimal: Decimal) -> Result<f64, ShellError> {
+        decimal
+            .simple_spanned(Span::unknown())
+            .coerce_into("testing")
+    }
+
+    #[test]
+    fn coerces_decimals_that_fit_in_a_float() {
+        assert_eq!(coerce(Decimal::new(15, 1)).unwrap(), 1.5);
+        assert_eq!(coerce(Decimal::from(-12345)).unwrap(), -12345.0);
+        assert_eq!(coerce(Decimal::new(0, 0)).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn allows_the_usual_float_rounding() {
+        let decimal = Decimal::from_str("0.1234567890123456789012345678").unwrap();
+
+        assert_eq!(coerce(decimal).unwrap(), 0.123_456_789_012_345_68);
+    }
+
+    #[test]
+    fn refuses_decimals_a_float_can_not_hold() {
+        assert!(coerce(Decimal::max_value()).is_err());
+        assert!(coerce(Decimal::min_value()).is_err());
+    }
+}
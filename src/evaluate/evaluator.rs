@@ -44,8 +44,8 @@ pub(crate) fn evaluate_baseline_expr(
         RawExpression::Variable(var) => evaluate_reference(var, scope, source),
         RawExpression::ExternalCommand(external) => evaluate_external(external, scope, source),
         RawExpression::Binary(binary) => {
-            let left = evaluate_baseline_expr(binary.left(), registry, scope, source)?;
-            let right = evaluate_baseline_expr(binary.right(), registry, scope, source)?;
+            let left = evaluate_operand(binary.left(), registry, scope, source)?;
+            let right = evaluate_operand(binary.right(), registry, scope, source)?;
 
             match left.compare(binary.op(), &*right) {
                 Ok(result) => Ok(Tagged::from_simple_spanned_item(
@@ -74,26 +74,7 @@ pub(crate) fn evaluate_baseline_expr(
         )),
         RawExpression::Path(path) => {
             let value = evaluate_baseline_expr(path.head(), registry, scope, source)?;
-            let mut item = value;
-
-            for name in path.tail() {
-                let next = item.get_data_by_key(name);
-
-                match next {
-                    None => {
-                        return Err(ShellError::missing_property(
-                            Description::from(item.tagged_type_name()),
-                            Description::from(name.clone()),
-                        ))
-                    }
-                    Some(next) => {
-                        item = Tagged::from_simple_spanned_item(
-                            next.clone().item,
-                            (expr.span().start, name.span().end),
-                        )
-                    }
-                };
-            }
+            let item = follow_path(value, path.tail(), expr.span())?;
 
             Ok(Tagged::from_simple_spanned_item(
                 item.item().clone(),
@@ -104,12 +85,63 @@ pub(crate) fn evaluate_baseline_expr(
     }
 }
 
+fn follow_path(
+    mut item: Tagged<Value>,
+    tail: &[Tagged<String>],
+    span: Span,
+) -> Result<Tagged<Value>, ShellError> {
+    for name in tail {
+        let next = item.get_data_by_key(name);
+
+        match next {
+            None => {
+                return Err(ShellError::missing_property(
+                    Description::from(item.tagged_type_name()),
+                    Description::from(name.clone()),
+                ))
+            }
+            Some(next) => {
+                item = Tagged::from_simple_spanned_item(
+                    next.clone().item,
+                    (span.start, name.span().end),
+                )
+            }
+        };
+    }
+
+    Ok(item)
+}
+
+// A path to a column the row doesn't have compares as nothing, so `age > 18`
+// leaves out rows without an age instead of stopping with an error
+fn evaluate_operand(
+    expr: &Expression,
+    registry: &CommandRegistry,
+    scope: &Scope,
+    source: &Text,
+) -> Result<Tagged<Value>, ShellError> {
+    match &expr.item {
+        RawExpression::Path(path) => {
+            let value = evaluate_baseline_expr(path.head(), registry, scope, source)?;
+
+            let item = match follow_path(value, path.tail(), expr.span()) {
+                Ok(item) => item.item,
+                Err(_) => Value::nothing(),
+            };
+
+            Ok(Tagged::from_simple_spanned_item(item, expr.span()))
+        }
+        _ => evaluate_baseline_expr(expr, registry, scope, source),
+    }
+}
+
 fn evaluate_literal(literal: Tagged<hir::Literal>, source: &Text) -> Tagged<Value> {
     let result = match literal.item {
         hir::Literal::Number(int) => int.into(),
         hir::Literal::Size(int, unit) => unit.compute(int),
         hir::Literal::String(span) => Value::string(span.slice(source)),
         hir::Literal::Bare => Value::string(literal.span().slice(source)),
+        hir::Literal::Nothing => Value::nothing(),
     };
 
     literal.map(|_| result)
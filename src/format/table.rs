@@ -1,4 +1,5 @@
 use crate::format::RenderView;
+use crate::object::base::column_union;
 use crate::object::Value;
 use crate::prelude::*;
 use derive_new::new;
@@ -17,24 +18,16 @@ pub struct TableView {
 }
 
 impl TableView {
-    fn merge_descriptors(values: &[Tagged<Value>]) -> Vec<String> {
-        let mut ret = vec![];
-        for value in values {
-            for desc in value.data_descriptors() {
-                if !ret.contains(&desc) {
-                    ret.push(desc);
-                }
-            }
-        }
-        ret
+    pub fn from_list(values: &[Tagged<Value>]) -> Option<TableView> {
+        TableView::from_list_with_width(values, textwrap::termwidth())
     }
 
-    pub fn from_list(values: &[Tagged<Value>]) -> Option<TableView> {
+    pub fn from_list_with_width(values: &[Tagged<Value>], width: usize) -> Option<TableView> {
         if values.len() == 0 {
             return None;
         }
 
-        let mut headers = TableView::merge_descriptors(values);
+        let mut headers = column_union(values);
 
         if headers.len() == 0 {
             headers.push("value".to_string());
@@ -91,7 +84,7 @@ impl TableView {
         }
 
         // Different platforms want different amounts of buffer, not sure why
-        let termwidth = std::cmp::max(textwrap::termwidth(), 20);
+        let termwidth = std::cmp::max(width, 20);
 
         // Make sure we have enough space for the columns we have
         let max_num_of_columns = termwidth / 10;
@@ -189,14 +182,8 @@ impl TableView {
 
         Some(TableView { headers, entries })
     }
-}
-
-impl RenderView for TableView {
-    fn render_view(&self, host: &mut dyn Host) -> Result<(), ShellError> {
-        if self.entries.len() == 0 {
-            return Ok(());
-        }
 
+    fn table(&self) -> Table {
         let mut table = Table::new();
         table.set_format(
             FormatBuilder::new()
@@ -228,7 +215,27 @@ impl RenderView for TableView {
             ));
         }
 
-        table.print_term(&mut *host.out_terminal()).unwrap();
+        table
+    }
+
+    /// Renders the table as plain text, without any colors, so it can be
+    /// passed along the pipeline.
+    pub fn render_string(&self) -> String {
+        if self.entries.len() == 0 {
+            return String::new();
+        }
+
+        self.table().to_string()
+    }
+}
+
+impl RenderView for TableView {
+    fn render_view(&self, host: &mut dyn Host) -> Result<(), ShellError> {
+        if self.entries.len() == 0 {
+            return Ok(());
+        }
+
+        self.table().print_term(&mut *host.out_terminal()).unwrap();
 
         Ok(())
     }
@@ -22,6 +22,7 @@ mod traits;
 mod utils;
 
 pub use crate::commands::command::{CallInfo, ReturnSuccess, ReturnValue};
+pub use crate::commands::first::Amount;
 pub use crate::context::{SourceMap, SpanSource};
 pub use crate::env::host::BasicHost;
 pub use crate::parser::hir::SyntaxType;
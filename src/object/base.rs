@@ -18,6 +18,7 @@ pub enum Primitive {
     Nothing,
     Int(i64),
     Decimal(Decimal),
+    BigInt(BigInt),
     Bytes(u64),
     String(String),
     Boolean(bool),
@@ -58,6 +59,7 @@ impl Primitive {
             Path(_) => "path",
             Int(_) => "int",
             Decimal(_) => "decimal",
+            BigInt(_) => "int",
             Bytes(_) => "bytes",
             String(_) => "string",
             Boolean(_) => "boolean",
@@ -76,6 +78,7 @@ impl Primitive {
             Int(int) => write!(f, "{}", int),
             Path(path) => write!(f, "{}", path.display()),
             Decimal(decimal) => write!(f, "{}", decimal),
+            BigInt(int) => write!(f, "{}", int),
             Bytes(bytes) => write!(f, "{}", bytes),
             String(string) => write!(f, "{:?}", string),
             Boolean(boolean) => write!(f, "{}", boolean),
@@ -114,6 +117,7 @@ impl Primitive {
             }
             Primitive::Int(i) => format!("{}", i),
             Primitive::Decimal(decimal) => format!("{}", decimal),
+            Primitive::BigInt(int) => format!("{}", int),
             Primitive::String(s) => format!("{}", s),
             Primitive::Boolean(b) => match (b, field_name) {
                 (true, None) => format!("Yes"),
@@ -130,7 +134,10 @@ impl Primitive {
     pub fn style(&self) -> &'static str {
         match self {
             Primitive::Bytes(0) => "c", // centre 'missing' indicator
-            Primitive::Int(_) | Primitive::Bytes(_) | Primitive::Decimal(_) => "r",
+            Primitive::Int(_)
+            | Primitive::Bytes(_)
+            | Primitive::Decimal(_)
+            | Primitive::BigInt(_) => "r",
             _ => "",
         }
     }
@@ -540,6 +547,20 @@ impl Value {
         operator: &Operator,
         other: &Value,
     ) -> Result<bool, (String, String)> {
+        // Nothing only equals nothing and is never ordered against anything, so
+        // an empty cell fails a comparison instead of making it an error
+        match (self, other) {
+            (Value::Primitive(Primitive::Nothing), _)
+            | (_, Value::Primitive(Primitive::Nothing)) => {
+                return Ok(match operator {
+                    Operator::Equal => self == other,
+                    Operator::NotEqual => self != other,
+                    _ => false,
+                })
+            }
+            _ => {}
+        }
+
         match operator {
             _ => {
                 let coerced = coerce_compare(self, other)?;
@@ -589,6 +610,7 @@ impl Value {
             Value::Primitive(Primitive::Boolean(x)) => Ok(format!("{}", x)),
             Value::Primitive(Primitive::Decimal(x)) => Ok(format!("{}", x)),
             Value::Primitive(Primitive::Int(x)) => Ok(format!("{}", x)),
+            Value::Primitive(Primitive::BigInt(x)) => Ok(format!("{}", x)),
             Value::Primitive(Primitive::Bytes(x)) => Ok(format!("{}", x)),
             // TODO: this should definitely be more general with better errors
             other => Err(ShellError::string(format!(
@@ -681,6 +703,22 @@ impl Tagged<Value> {
     }
 }
 
+// Every column held by any of the rows, in the order each is first seen, so
+// rows with different columns can be written under one header
+pub(crate) fn column_union(values: &[Tagged<Value>]) -> Vec<String> {
+    let mut columns = vec![];
+
+    for value in values {
+        for column in value.data_descriptors() {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+    }
+
+    columns
+}
+
 pub(crate) fn select_fields(obj: &Value, fields: &[String], tag: impl Into<Tag>) -> Tagged<Value> {
     let mut out = TaggedDictBuilder::new(tag);
 
@@ -798,6 +836,7 @@ pub(crate) fn find(obj: &Value, field: &str, op: &Operator, rhs: &Value) -> bool
 enum CompareValues {
     Ints(i64, i64),
     Decimals(Decimal, Decimal),
+    BigInts(BigInt, BigInt),
     Bytes(u64, u64),
     String(String, String),
 }
@@ -807,6 +846,7 @@ impl CompareValues {
         match self {
             CompareValues::Ints(left, right) => left.cmp(right),
             CompareValues::Decimals(left, right) => left.cmp(right),
+            CompareValues::BigInts(left, right) => left.cmp(right),
             CompareValues::Bytes(left, right) => left.cmp(right),
             CompareValues::String(left, right) => left.cmp(right),
         }
@@ -836,6 +876,9 @@ fn coerce_compare_primitive(
         (Decimal(left), Bytes(right)) => {
             CompareValues::Decimals(*left, rust_decimal::Decimal::from(*right))
         }
+        (BigInt(left), BigInt(right)) => CompareValues::BigInts(left.clone(), right.clone()),
+        (BigInt(left), Int(right)) => CompareValues::BigInts(left.clone(), (*right).into()),
+        (Int(left), BigInt(right)) => CompareValues::BigInts((*left).into(), right.clone()),
         (Bytes(left), Int(right)) => CompareValues::Bytes(*left, *right as u64),
         (Bytes(left), Decimal(right)) => {
             CompareValues::Decimals(rust_decimal::Decimal::from(*left), *right)
@@ -844,3 +887,32 @@ fn coerce_compare_primitive(
         _ => return Err((left.type_name(), right.type_name())),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{column_union, Value};
+    use crate::object::TaggedDictBuilder;
+    use crate::prelude::*;
+
+    fn row(columns: &[&str]) -> Tagged<Value> {
+        let mut row = TaggedDictBuilder::new(Tag::unknown());
+        for column in columns {
+            row.insert(*column, Value::string(*column));
+        }
+        row.into_tagged_value()
+    }
+
+    #[test]
+    fn column_union_keeps_the_order_columns_are_first_seen() {
+        let rows = vec![
+            row(&["name", "size"]),
+            row(&["size", "modified"]),
+            row(&["type", "name"]),
+        ];
+
+        assert_eq!(
+            column_union(&rows),
+            vec!["name", "size", "modified", "type"]
+        );
+    }
+}
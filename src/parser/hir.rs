@@ -163,6 +163,10 @@ impl Expression {
         Tagged::from_simple_spanned_item(RawExpression::Literal(Literal::Bare), span.into())
     }
 
+    pub(crate) fn nothing(span: impl Into<Span>) -> Expression {
+        Tagged::from_simple_spanned_item(RawExpression::Literal(Literal::Nothing), span.into())
+    }
+
     pub(crate) fn variable(inner: impl Into<Span>, outer: impl Into<Span>) -> Expression {
         Tagged::from_simple_spanned_item(
             RawExpression::Variable(Variable::Other(inner.into())),
@@ -232,6 +236,7 @@ pub enum Literal {
     Size(Number, Unit),
     String(Span),
     Bare,
+    Nothing,
 }
 
 impl ToDebug for Tagged<&Literal> {
@@ -241,6 +246,7 @@ impl ToDebug for Tagged<&Literal> {
             Literal::Size(number, unit) => write!(f, "{:?}{:?}", *number, unit),
             Literal::String(span) => write!(f, "{}", span.slice(source)),
             Literal::Bare => write!(f, "{}", self.span().slice(source)),
+            Literal::Nothing => write!(f, "null"),
         }
     }
 }
@@ -252,6 +258,7 @@ impl Literal {
             Literal::Size(..) => "size",
             Literal::String(..) => "string",
             Literal::Bare => "string",
+            Literal::Nothing => "nothing",
         }
     }
 }
@@ -136,6 +136,39 @@ pub fn baseline_parse_next_expr(
 
     let first = baseline_parse_semantic_token(next, context, source)?;
 
+    // `contains` and `is` aren't operators, so a block such as `name contains "test"`
+    // or `size is int` is passed on whole as the text of a predicate instead
+    if let (SyntaxType::Block, hir::RawExpression::Literal(hir::Literal::Bare)) =
+        (syntax_type, &first.item)
+    {
+        let keyword = match tokens.peek() {
+            Some(TokenNode::Token(Tagged {
+                item: RawToken::Bare,
+                tag,
+            })) => match tag.span.slice(source) {
+                word @ "contains" | word @ "is" => Some((word, tag.span)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some((keyword, keyword_span)) = keyword {
+            tokens.next();
+
+            return match tokens.next() {
+                Some(value) => {
+                    let span = (first.span().start, value.span().end);
+                    Ok(hir::Expression::string(span, span))
+                }
+                None => Err(ShellError::labeled_error(
+                    format!("Expected something after '{}'", keyword),
+                    keyword,
+                    keyword_span,
+                )),
+            };
+        }
+    }
+
     let possible_op = tokens.peek();
 
     let op = match possible_op {
@@ -156,6 +189,16 @@ pub fn baseline_parse_next_expr(
         Some(token) => baseline_parse_semantic_token(token, context, source)?,
     };
 
+    // A bare `null` after an operator, as in `age != null`, stands for nothing
+    let second = match second.item {
+        hir::RawExpression::Literal(hir::Literal::Bare)
+            if second.span().slice(source) == "null" =>
+        {
+            hir::Expression::nothing(second.span())
+        }
+        _ => second,
+    };
+
     // We definitely have a binary expression here -- let's see if we should coerce it into a block
 
     match syntax_type {
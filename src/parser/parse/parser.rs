@@ -341,8 +341,11 @@ pub fn size(input: NomSpan) -> IResult<NomSpan, TokenNode> {
         } else {
             let end = input.offset;
 
-            // Check to make sure there is no trailing parseable characters
-            if let Ok((input, Some(extra))) = opt(bare)(input) {
+            // Check to make sure there is no trailing parseable characters. This
+            // includes ones a bare word can't start with, so 50% is left as bare
+            let trailing: IResult<NomSpan, NomSpan, (NomSpan, nom::error::ErrorKind)> =
+                take_while1(is_bare_char)(input);
+            if let Ok((input, _)) = trailing {
                 return Err(nom::Err::Error((input, nom::error::ErrorKind::Char)));
             }
 
@@ -795,6 +798,11 @@ mod tests {
             parsers [ bare ]
             r"C:\Code\-testing\my_tests.js" -> 0..28 { Bare }
         }
+
+        assert_leaf! {
+            parsers [ bare ]
+            "50%" -> 0..3 { Bare }
+        }
     }
 
     #[test]
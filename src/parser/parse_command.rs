@@ -155,7 +155,7 @@ fn parse_command_tail(
             }
 
             PositionalType::Optional(..) => {
-                if tail.len() == 0 {
+                if tail.at_end() {
                     break;
                 }
             }
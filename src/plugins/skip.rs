@@ -1,42 +1,32 @@
 use nu::{
-    serve_plugin, CallInfo, Plugin, Primitive, ReturnSuccess, ReturnValue, ShellError, Signature,
+    serve_plugin, Amount, CallInfo, Plugin, ReturnSuccess, ReturnValue, ShellError, Signature,
     SyntaxType, Tagged, Value,
 };
 
 struct Skip {
-    skip_amount: i64,
+    amount: Amount,
+    buffered: Vec<Tagged<Value>>,
 }
 impl Skip {
     fn new() -> Skip {
-        Skip { skip_amount: 0 }
+        Skip {
+            amount: Amount::Rows(0),
+            buffered: vec![],
+        }
     }
 }
 
 impl Plugin for Skip {
     fn config(&mut self) -> Result<Signature, ShellError> {
        Ok(Signature::build("skip")
-            .desc("Skip a number of rows")
-            .rest(SyntaxType::Number)
+            .desc("Skip a number of rows, or a share of them given as a percentage like '10%'")
+            .rest(SyntaxType::Any)
             .filter())
     }
     fn begin_filter(&mut self, call_info: CallInfo) -> Result<Vec<ReturnValue>, ShellError> {
         if let Some(args) = call_info.args.positional {
             for arg in args {
-                match arg {
-                    Tagged {
-                        item: Value::Primitive(Primitive::Int(i)),
-                        ..
-                    } => {
-                        self.skip_amount = i;
-                    }
-                    _ => {
-                        return Err(ShellError::labeled_error(
-                            "Unrecognized type in params",
-                            "expected an integer",
-                            arg.span(),
-                        ))
-                    }
-                }
+                self.amount = Amount::from_value(&arg)?;
             }
         }
 
@@ -44,11 +34,34 @@ impl Plugin for Skip {
     }
 
     fn filter(&mut self, input: Tagged<Value>) -> Result<Vec<ReturnValue>, ShellError> {
-        if self.skip_amount == 0 {
-            Ok(vec![ReturnSuccess::value(input)])
-        } else {
-            self.skip_amount -= 1;
-            Ok(vec![])
+        // A share of the rows is only known once every row has arrived, so
+        // unlike a count a percentage holds the rows back until the end
+        match self.amount {
+            Amount::Percent(_) => {
+                self.buffered.push(input);
+                Ok(vec![])
+            }
+            Amount::Rows(0) => Ok(vec![ReturnSuccess::value(input)]),
+            Amount::Rows(ref mut rows) => {
+                *rows -= 1;
+                Ok(vec![])
+            }
+        }
+    }
+
+    fn end_filter(&mut self) -> Result<Vec<ReturnValue>, ShellError> {
+        match self.amount {
+            Amount::Percent(_) => {
+                let skipped = self.amount.of(self.buffered.len());
+
+                Ok(self
+                    .buffered
+                    .drain(..)
+                    .skip(skipped)
+                    .map(ReturnSuccess::value)
+                    .collect())
+            }
+            Amount::Rows(_) => Ok(vec![]),
         }
     }
 }
@@ -10,6 +10,8 @@ enum Action {
     Upcase,
     ToInteger,
     Replace(ReplaceAction),
+    Substring(i64, i64),
+    Pad(PadAction, usize),
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -18,11 +20,18 @@ enum ReplaceAction {
     FindAndReplace,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum PadAction {
+    Left,
+    Right,
+}
+
 struct Str {
     field: Option<String>,
     params: Option<Vec<String>>,
     error: Option<String>,
     action: Option<Action>,
+    fill: char,
 }
 
 impl Str {
@@ -32,6 +41,7 @@ impl Str {
             params: Some(Vec::<String>::new()),
             error: None,
             action: None,
+            fill: ' ',
         }
     }
 
@@ -56,6 +66,37 @@ impl Str {
                     }
                 }
             },
+            Some(Action::Substring(start, end)) => {
+                // Indices count characters, not bytes, and negative ones count
+                // back from the end. Bounds past either end are clamped.
+                let chars: Vec<char> = input.chars().collect();
+                let len = chars.len() as i64;
+                let clamp = |idx: i64| {
+                    let idx = if idx < 0 { len + idx } else { idx };
+                    idx.max(0).min(len) as usize
+                };
+
+                let (start, end) = (clamp(*start), clamp(*end));
+                if start < end {
+                    Value::string(chars[start..end].iter().collect::<String>())
+                } else {
+                    Value::string("")
+                }
+            }
+            Some(Action::Pad(side, width)) => {
+                // Widths count characters, and strings already that wide are
+                // left as they are
+                let len = input.chars().count();
+                if len >= *width {
+                    Value::string(input)
+                } else {
+                    let padding: String = std::iter::repeat(self.fill).take(width - len).collect();
+                    match side {
+                        PadAction::Left => Value::string(padding + input),
+                        PadAction::Right => Value::string(input.to_string() + &padding),
+                    }
+                }
+            }
             None => Value::string(input),
         };
 
@@ -124,8 +165,46 @@ impl Str {
         }
     }
 
+    fn for_substring(&mut self, range: &str) {
+        let bounds: Vec<Option<i64>> = range.split(',').map(|b| b.trim().parse().ok()).collect();
+
+        match bounds.as_slice() {
+            [Some(start), Some(end)] => {
+                if self.permit() {
+                    self.action = Some(Action::Substring(*start, *end))
+                } else {
+                    self.log_error("can only apply one")
+                }
+            }
+            _ => self.log_error("substring needs a range such as 0,3"),
+        }
+    }
+
+    fn for_pad(&mut self, side: PadAction, width: &str) {
+        match width.trim().parse::<usize>() {
+            Ok(width) => {
+                if self.permit() {
+                    self.action = Some(Action::Pad(side, width))
+                } else {
+                    self.log_error("can only apply one")
+                }
+            }
+            Err(_) => self.log_error("padding needs a width such as 8"),
+        }
+    }
+
+    // Padding is made of spaces unless another character is given
+    fn for_fill(&mut self, fill: &str) {
+        let mut chars = fill.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(fill), None) => self.fill = fill,
+            _ => self.log_error("fill needs a single character such as 0"),
+        }
+    }
+
     pub fn usage() -> &'static str {
-        "Usage: str field [--downcase|--upcase|--to-int|--replace|--find-replace]"
+        "Usage: str field [--downcase|--upcase|--to-int|--replace|--find-replace|--substring start,end|--pad-left width|--pad-right width] [--fill char]"
     }
 }
 
@@ -157,10 +236,11 @@ impl Str {
                     Str::usage()
                 ))),
             },
-            x => Err(ShellError::string(format!(
-                "Unrecognized type in stream: {:?}",
-                x
-            ))),
+            _ => Err(ShellError::labeled_error(
+                "Unrecognized type in stream",
+                "expected a string",
+                value.span(),
+            )),
         }
     }
 }
@@ -174,6 +254,10 @@ impl Plugin for Str {
             .switch("to-int")
             .switch("replace")
             .switch("find-replace")
+            .named("substring", SyntaxType::Any)
+            .named("pad-left", SyntaxType::Any)
+            .named("pad-right", SyntaxType::Any)
+            .named("fill", SyntaxType::Any)
             .rest(SyntaxType::Member)
             .filter())
     }
@@ -196,6 +280,66 @@ impl Plugin for Str {
         if args.has("find-replace") {
             self.for_replace(ReplaceAction::FindAndReplace);
         }
+        if let Some(range) = args.get("substring") {
+            match range {
+                Tagged {
+                    item: Value::Primitive(Primitive::String(s)),
+                    ..
+                } => self.for_substring(s),
+                _ => {
+                    return Err(ShellError::labeled_error(
+                        "Unrecognized type in params",
+                        "expected a range such as 0,3",
+                        range.span(),
+                    ))
+                }
+            }
+        }
+
+        for (flag, side) in &[
+            ("pad-left", PadAction::Left),
+            ("pad-right", PadAction::Right),
+        ] {
+            if let Some(width) = args.get(flag) {
+                match width {
+                    Tagged {
+                        item: Value::Primitive(Primitive::Int(i)),
+                        ..
+                    } => self.for_pad(side.clone(), &i.to_string()),
+                    Tagged {
+                        item: Value::Primitive(Primitive::String(s)),
+                        ..
+                    } => self.for_pad(side.clone(), s),
+                    _ => {
+                        return Err(ShellError::labeled_error(
+                            "Unrecognized type in params",
+                            "expected a width such as 8",
+                            width.span(),
+                        ))
+                    }
+                }
+            }
+        }
+
+        if let Some(fill) = args.get("fill") {
+            match fill {
+                Tagged {
+                    item: Value::Primitive(Primitive::Int(i)),
+                    ..
+                } => self.for_fill(&i.to_string()),
+                Tagged {
+                    item: Value::Primitive(Primitive::String(s)),
+                    ..
+                } => self.for_fill(s),
+                _ => {
+                    return Err(ShellError::labeled_error(
+                        "Unrecognized type in params",
+                        "expected a character such as 0",
+                        fill.span(),
+                    ))
+                }
+            }
+        }
 
         if let Some(possible_field) = args.nth(0) {
             match possible_field {
@@ -216,6 +360,8 @@ impl Plugin for Str {
                     Some(Action::Downcase)
                     | Some(Action::Upcase)
                     | Some(Action::ToInteger)
+                    | Some(Action::Substring(..))
+                    | Some(Action::Pad(..))
                     | None => {
                         self.for_field(&s);
                     }
@@ -259,7 +405,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
 
-    use super::{Action, ReplaceAction, Str};
+    use super::{Action, PadAction, ReplaceAction, Str};
     use indexmap::IndexMap;
     use nu::{
         CallInfo, EvaluatedArgs, Plugin, Primitive, ReturnSuccess, SourceMap, Span, Tag, Tagged,
@@ -297,6 +443,14 @@ mod tests {
             self
         }
 
+        fn with_named_parameter(&mut self, name: &str, value: &str) -> &mut Self {
+            self.flags.insert(
+                name.to_string(),
+                Value::string(value).simple_spanned(Span::unknown()),
+            );
+            self
+        }
+
         fn with_parameter(&mut self, name: &str) -> &mut Self {
             self.positionals
                 .push(Value::string(name.to_string()).simple_spanned(Span::unknown()));
@@ -328,7 +482,16 @@ mod tests {
 
         let configured = plugin.config().unwrap();
 
-        for action_flag in &["downcase", "upcase", "to-int", "replace", "find-replace"] {
+        for action_flag in &[
+            "downcase",
+            "upcase",
+            "to-int",
+            "replace",
+            "find-replace",
+            "substring",
+            "pad-left",
+            "pad-right",
+        ] {
             assert!(configured.named.get(*action_flag).is_some());
         }
     }
@@ -389,6 +552,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn str_plugin_accepts_substring() {
+        let mut plugin = Str::new();
+
+        assert!(plugin
+            .begin_filter(
+                CallStub::new()
+                    .with_named_parameter("substring", "0,3")
+                    .create()
+            )
+            .is_ok());
+        assert_eq!(plugin.action.unwrap(), Action::Substring(0, 3));
+    }
+
+    #[test]
+    fn str_plugin_accepts_pad() {
+        let mut plugin = Str::new();
+
+        assert!(plugin
+            .begin_filter(
+                CallStub::new()
+                    .with_named_parameter("pad-left", "8")
+                    .create()
+            )
+            .is_ok());
+        assert_eq!(plugin.action.unwrap(), Action::Pad(PadAction::Left, 8));
+    }
+
+    #[test]
+    fn str_plugin_pads_with_the_fill_flag() {
+        let mut plugin = Str::new();
+
+        assert!(plugin
+            .begin_filter(
+                CallStub::new()
+                    .with_named_parameter("pad-left", "4")
+                    .with_named_parameter("fill", "0")
+                    .create()
+            )
+            .is_ok());
+
+        assert_eq!(plugin.field, None);
+        assert_eq!(plugin.apply("42").unwrap(), Value::string("0042"));
+    }
+
+    #[test]
+    fn str_plugin_pads_a_field_without_a_fill() {
+        let mut plugin = Str::new();
+
+        assert!(plugin
+            .begin_filter(
+                CallStub::new()
+                    .with_named_parameter("pad-left", "4")
+                    .with_parameter("code")
+                    .create()
+            )
+            .is_ok());
+
+        assert_eq!(plugin.field, Some("code".to_string()));
+        assert_eq!(plugin.apply("42").unwrap(), Value::string("  42"));
+    }
+
     #[test]
     fn str_plugin_accepts_field() {
         let mut plugin = Str::new();
@@ -441,6 +666,57 @@ mod tests {
         assert_eq!(strutils.apply("9999").unwrap(), Value::int(9999 as i64));
     }
 
+    #[test]
+    fn str_substring_counts_characters() {
+        let mut strutils = Str::new();
+        strutils.for_substring("1,3");
+        assert_eq!(strutils.apply("ñandú").unwrap(), Value::string("an"));
+    }
+
+    #[test]
+    fn str_substring_counts_negative_indices_from_the_end() {
+        let mut strutils = Str::new();
+        strutils.for_substring("-3,-1");
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string("re"));
+    }
+
+    #[test]
+    fn str_substring_clamps_out_of_range_bounds() {
+        let mut strutils = Str::new();
+        strutils.for_substring("2,100");
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string("dres"));
+
+        let mut strutils = Str::new();
+        strutils.for_substring("4,2");
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string(""));
+    }
+
+    #[test]
+    fn str_pads_to_a_width_with_spaces_by_default() {
+        let mut strutils = Str::new();
+        strutils.for_pad(PadAction::Left, "5");
+        assert_eq!(strutils.apply("ñu").unwrap(), Value::string("   ñu"));
+
+        let mut strutils = Str::new();
+        strutils.for_pad(PadAction::Right, "5");
+        assert_eq!(strutils.apply("ñu").unwrap(), Value::string("ñu   "));
+    }
+
+    #[test]
+    fn str_pads_with_the_fill_given() {
+        let mut strutils = Str::new();
+        strutils.for_pad(PadAction::Left, "4");
+        strutils.for_fill("0");
+        assert_eq!(strutils.apply("42").unwrap(), Value::string("0042"));
+    }
+
+    #[test]
+    fn str_pad_leaves_strings_already_wide_enough() {
+        let mut strutils = Str::new();
+        strutils.for_pad(PadAction::Right, "3");
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string("andres"));
+    }
+
     #[test]
     fn str_replace() {
         let mut strutils = Str::new();
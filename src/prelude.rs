@@ -76,6 +76,7 @@ pub(crate) use crate::Text;
 pub(crate) use futures::stream::BoxStream;
 pub(crate) use futures::{FutureExt, Stream, StreamExt};
 pub(crate) use futures_async_stream::async_stream_block;
+pub(crate) use num_bigint::BigInt;
 pub(crate) use num_traits::cast::{FromPrimitive, ToPrimitive};
 pub(crate) use rust_decimal::Decimal;
 #[allow(unused)]
@@ -3,6 +3,7 @@ use crate::commands::cp::CopyArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
+use crate::commands::touch::TouchArgs;
 use crate::context::SourceMap;
 use crate::object::dir_entry_dict;
 use crate::prelude::*;
@@ -67,6 +68,97 @@ impl FilesystemShell {
     }
 }
 
+// Paths from the walk are already canonical, so a symlink inside the tree is
+// still the link itself here and is recreated instead of being followed.
+fn copy_entry(src: &Path, dst: &Path, span: Span) -> Result<(), ShellError> {
+    let copied = match std::fs::symlink_metadata(src) {
+        Ok(ref metadata) if metadata.file_type().is_symlink() => copy_symlink(src, dst),
+        _ if src.is_dir() && dst.exists() => Ok(()),
+        _ if src.is_dir() => std::fs::create_dir_all(dst),
+        _ if src.is_file() => std::fs::copy(src, dst).map(|_| ()),
+        _ => Ok(()),
+    };
+
+    copied.map_err(|e| {
+        ShellError::labeled_error(
+            format!("Could not copy {}: {}", src.display(), e),
+            e.to_string(),
+            span,
+        )
+    })
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(src)?, dst)
+}
+
+#[cfg(windows)]
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(src)?;
+
+    if src.is_dir() {
+        std::os::windows::fs::symlink_dir(target, dst)
+    } else {
+        std::os::windows::fs::symlink_file(target, dst)
+    }
+}
+
+#[cfg(unix)]
+const CROSS_DEVICE_ERROR: i32 = 18; // EXDEV
+#[cfg(windows)]
+const CROSS_DEVICE_ERROR: i32 = 17; // ERROR_NOT_SAME_DEVICE
+
+fn copy_tree(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let metadata = std::fs::symlink_metadata(src)?;
+
+    if metadata.file_type().is_symlink() {
+        copy_symlink(src, dst)
+    } else if metadata.is_dir() {
+        std::fs::create_dir_all(dst)?;
+
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+
+        Ok(())
+    } else {
+        std::fs::copy(src, dst).map(|_| ())
+    }
+}
+
+// The original is only removed once the copy succeeded
+fn copy_then_remove(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if let Err(e) = copy_tree(src, dst) {
+        return Err(std::io::Error::new(
+            e.kind(),
+            format!("{} (the original was left in place)", e),
+        ));
+    }
+
+    let removed = if std::fs::symlink_metadata(src)?.is_dir() {
+        std::fs::remove_dir_all(src)
+    } else {
+        std::fs::remove_file(src)
+    };
+
+    removed.map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("copied, but could not remove the original: {}", e),
+        )
+    })
+}
+
+// A rename can't cross filesystems, so in that case the entry is copied over
+fn move_entry(src: &Path, dst: &Path) -> std::io::Result<()> {
+    match std::fs::rename(src, dst) {
+        Err(ref e) if e.raw_os_error() == Some(CROSS_DEVICE_ERROR) => copy_then_remove(src, dst),
+        result => result,
+    }
+}
+
 impl Shell for FilesystemShell {
     fn name(&self, _source_map: &SourceMap) -> String {
         "filesystem".to_string()
@@ -275,18 +367,7 @@ impl Shell for FilesystemShell {
                     let sources = sources.paths_applying_with(strategy)?;
 
                     for (ref src, ref dst) in sources {
-                        if src.is_file() {
-                            match std::fs::copy(src, dst) {
-                                Err(e) => {
-                                    return Err(ShellError::labeled_error(
-                                        e.to_string(),
-                                        e.to_string(),
-                                        name_span,
-                                    ));
-                                }
-                                Ok(o) => o,
-                            };
-                        }
+                        copy_entry(src, dst, name_span)?;
                     }
                 }
 
@@ -303,9 +384,9 @@ impl Shell for FilesystemShell {
                             Ok(o) => o,
                         };
 
-                        let strategy = |(source_file, depth_level)| {
+                        let strategy = |(source_file, depth_level): (PathBuf, usize)| {
                             let mut new_dst = destination.clone();
-                            let path = dunce::canonicalize(&source_file)?;
+                            let path = source_file.clone();
 
                             let mut comps: Vec<_> = path
                                 .components()
@@ -326,33 +407,7 @@ impl Shell for FilesystemShell {
                         let sources = sources.paths_applying_with(strategy)?;
 
                         for (ref src, ref dst) in sources {
-                            if src.is_dir() {
-                                if !dst.exists() {
-                                    match std::fs::create_dir_all(dst) {
-                                        Err(e) => {
-                                            return Err(ShellError::labeled_error(
-                                                e.to_string(),
-                                                e.to_string(),
-                                                name_span,
-                                            ));
-                                        }
-                                        Ok(o) => o,
-                                    };
-                                }
-                            }
-
-                            if src.is_file() {
-                                match std::fs::copy(src, dst) {
-                                    Err(e) => {
-                                        return Err(ShellError::labeled_error(
-                                            e.to_string(),
-                                            e.to_string(),
-                                            name_span,
-                                        ));
-                                    }
-                                    Ok(o) => o,
-                                };
-                            }
+                            copy_entry(src, dst, name_span)?;
                         }
                     } else {
                         match entry.file_name() {
@@ -377,9 +432,9 @@ impl Shell for FilesystemShell {
                             Ok(o) => o,
                         };
 
-                        let strategy = |(source_file, depth_level)| {
+                        let strategy = |(source_file, depth_level): (PathBuf, usize)| {
                             let mut new_dst = dunce::canonicalize(&destination)?;
-                            let path = dunce::canonicalize(&source_file)?;
+                            let path = source_file.clone();
 
                             let mut comps: Vec<_> = path
                                 .components()
@@ -400,33 +455,7 @@ impl Shell for FilesystemShell {
                         let sources = sources.paths_applying_with(strategy)?;
 
                         for (ref src, ref dst) in sources {
-                            if src.is_dir() {
-                                if !dst.exists() {
-                                    match std::fs::create_dir_all(dst) {
-                                        Err(e) => {
-                                            return Err(ShellError::labeled_error(
-                                                e.to_string(),
-                                                e.to_string(),
-                                                name_span,
-                                            ));
-                                        }
-                                        Ok(o) => o,
-                                    };
-                                }
-                            }
-
-                            if src.is_file() {
-                                match std::fs::copy(src, dst) {
-                                    Err(e) => {
-                                        return Err(ShellError::labeled_error(
-                                            e.to_string(),
-                                            e.to_string(),
-                                            name_span,
-                                        ));
-                                    }
-                                    Ok(o) => o,
-                                };
-                            }
+                            copy_entry(src, dst, name_span)?;
                         }
                     }
                 }
@@ -608,7 +637,7 @@ impl Shell for FilesystemShell {
                 }
 
                 if entry.is_file() {
-                    match std::fs::rename(&entry, &destination) {
+                    match move_entry(&entry, &destination) {
                         Err(e) => {
                             return Err(ShellError::labeled_error(
                                 format!(
@@ -653,7 +682,7 @@ impl Shell for FilesystemShell {
                     };
                     #[cfg(not(windows))]
                     {
-                        match std::fs::rename(&entry, &destination) {
+                        match move_entry(&entry, &destination) {
                             Err(e) => {
                                 return Err(ShellError::labeled_error(
                                     format!(
@@ -730,7 +759,7 @@ impl Shell for FilesystemShell {
                             }
 
                             if src.is_file() {
-                                match std::fs::rename(src, dst) {
+                                match move_entry(src, dst) {
                                     Err(e) => {
                                         return Err(ShellError::labeled_error(
                                             format!(
@@ -809,7 +838,7 @@ impl Shell for FilesystemShell {
                         to.push(entry_file_name);
 
                         if entry.is_file() {
-                            match std::fs::rename(&entry, &to) {
+                            match move_entry(&entry, &to) {
                                 Err(e) => {
                                     return Err(ShellError::labeled_error(
                                         format!(
@@ -846,102 +875,178 @@ impl Shell for FilesystemShell {
 
     fn rm(
         &self,
-        RemoveArgs { target, recursive }: RemoveArgs,
+        RemoveArgs {
+            target,
+            rest,
+            recursive,
+            trash: move_to_trash,
+        }: RemoveArgs,
         name: Span,
         path: &str,
     ) -> Result<OutputStream, ShellError> {
         let name_span = name;
+        let cwd = path;
 
-        if target.item.to_str() == Some(".") || target.item.to_str() == Some("..") {
-            return Err(ShellError::labeled_error(
-                "Remove aborted. \".\" or \"..\" may not be removed.",
-                "Remove aborted. \".\" or \"..\" may not be removed.",
-                target.span(),
-            ));
-        }
+        // Targets are removed in order, stopping at the first one that fails
+        for target in std::iter::once(target).chain(rest) {
+            if target.item.to_str() == Some(".") || target.item.to_str() == Some("..") {
+                return Err(ShellError::labeled_error(
+                    "Remove aborted. \".\" or \"..\" may not be removed.",
+                    "Remove aborted. \".\" or \"..\" may not be removed.",
+                    target.span(),
+                ));
+            }
 
-        let mut path = PathBuf::from(path);
+            let mut path = PathBuf::from(cwd);
 
-        path.push(&target.item);
+            path.push(&target.item);
 
-        let file = path.to_string_lossy();
+            let file = path.to_string_lossy();
 
-        let entries: Vec<_> = match glob::glob(&path.to_string_lossy()) {
-            Ok(files) => files.collect(),
-            Err(_) => {
-                return Err(ShellError::labeled_error(
-                    "Invalid pattern.",
-                    "Invalid pattern.",
-                    target.tag,
-                ))
-            }
-        };
+            let entries: Vec<_> = match glob::glob(&path.to_string_lossy()) {
+                Ok(files) => files.collect(),
+                Err(_) => {
+                    return Err(ShellError::labeled_error(
+                        "Invalid pattern.",
+                        "Invalid pattern.",
+                        target.tag,
+                    ))
+                }
+            };
 
-        if entries.len() == 1 {
-            if let Ok(entry) = &entries[0] {
-                if entry.is_dir() {
-                    let mut source_dir: FileStructure = FileStructure::new();
+            if entries.len() == 1 {
+                if let Ok(entry) = &entries[0] {
+                    if entry.is_dir() {
+                        let mut source_dir: FileStructure = FileStructure::new();
 
-                    source_dir.walk_decorate(&entry)?;
+                        source_dir.walk_decorate(&entry)?;
 
-                    if source_dir.contains_files() && !recursive.item {
-                        return Err(ShellError::labeled_error(
-                            format!("{:?} is a directory. Try using \"--recursive\".", file),
-                            format!("{:?} is a directory. Try using \"--recursive\".", file),
-                            target.span(),
-                        ));
+                        if source_dir.contains_files() && !recursive.item {
+                            return Err(ShellError::labeled_error(
+                                format!("{:?} is a directory. Try using \"--recursive\".", file),
+                                format!("{:?} is a directory. Try using \"--recursive\".", file),
+                                target.span(),
+                            ));
+                        }
                     }
                 }
             }
-        }
 
-        for entry in entries {
-            match entry {
-                Ok(path) => {
-                    let path_file_name = {
-                        match path.file_name() {
-                            Some(name) => PathBuf::from(name),
-                            None => {
-                                return Err(ShellError::labeled_error(
-                                    "Remove aborted. Not a valid path",
-                                    "Remove aborted. Not a valid path",
-                                    name_span,
-                                ))
+            for entry in entries {
+                match entry {
+                    Ok(path) => {
+                        let path_file_name = {
+                            match path.file_name() {
+                                Some(name) => PathBuf::from(name),
+                                None => {
+                                    return Err(ShellError::labeled_error(
+                                        "Remove aborted. Not a valid path",
+                                        "Remove aborted. Not a valid path",
+                                        name_span,
+                                    ))
+                                }
                             }
-                        }
-                    };
+                        };
 
-                    let mut source_dir: FileStructure = FileStructure::new();
+                        let mut source_dir: FileStructure = FileStructure::new();
 
-                    source_dir.walk_decorate(&path)?;
+                        source_dir.walk_decorate(&path)?;
 
-                    if source_dir.contains_more_than_one_file() && !recursive.item {
-                        return Err(ShellError::labeled_error(
-                            format!(
-                                "Directory {:?} found somewhere inside. Try using \"--recursive\".",
-                                path_file_name
-                            ),
-                            format!(
-                                "Directory {:?} found somewhere inside. Try using \"--recursive\".",
-                                path_file_name
-                            ),
-                            target.span(),
-                        ));
-                    }
+                        if source_dir.contains_more_than_one_file() && !recursive.item {
+                            return Err(ShellError::labeled_error(
+                                format!(
+                                    "Directory {:?} found somewhere inside. Try using \"--recursive\".",
+                                    path_file_name
+                                ),
+                                format!(
+                                    "Directory {:?} found somewhere inside. Try using \"--recursive\".",
+                                    path_file_name
+                                ),
+                                target.span(),
+                            ));
+                        }
+
+                        if move_to_trash.item {
+                            if let Err(e) = trash::remove(&path) {
+                                return Err(ShellError::labeled_error(
+                                    format!("Could not move {:?} to the trash", path_file_name),
+                                    format!("{:?}", e),
+                                    target.span(),
+                                ));
+                            }
+                        } else {
+                            let removed = if path.is_dir() {
+                                std::fs::remove_dir_all(&path)
+                            } else {
+                                std::fs::remove_file(&path)
+                            };
 
-                    if path.is_dir() {
-                        std::fs::remove_dir_all(&path)?;
-                    } else if path.is_file() {
-                        std::fs::remove_file(&path)?;
+                            if let Err(e) = removed {
+                                return Err(ShellError::labeled_error(
+                                    format!("Could not remove {:?}: {}", path_file_name, e),
+                                    e.to_string(),
+                                    target.span(),
+                                ));
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        return Err(ShellError::labeled_error(
+                            format!("Remove aborted. {:}", e.to_string()),
+                            format!("Remove aborted. {:}", e.to_string()),
+                            name_span,
+                        ))
                     }
                 }
-                Err(e) => {
-                    return Err(ShellError::labeled_error(
-                        format!("Remove aborted. {:}", e.to_string()),
-                        format!("Remove aborted. {:}", e.to_string()),
-                        name_span,
-                    ))
-                }
+            }
+        }
+
+        Ok(OutputStream::empty())
+    }
+
+    fn touch(
+        &self,
+        TouchArgs { rest: files }: TouchArgs,
+        name: Span,
+        path: &str,
+    ) -> Result<OutputStream, ShellError> {
+        let full_path = PathBuf::from(path);
+
+        if files.is_empty() {
+            return Err(ShellError::labeled_error(
+                "touch requires file paths",
+                "needs parameter",
+                name,
+            ));
+        }
+
+        for file in files.iter() {
+            let touch_at = {
+                let mut loc = full_path.clone();
+                loc.push(&file.item);
+                loc
+            };
+
+            // Opening for writing fails on a read-only file, so only new files are opened
+            let touched = if touch_at.exists() {
+                filetime::set_file_mtime(
+                    &touch_at,
+                    filetime::FileTime::from_system_time(std::time::SystemTime::now()),
+                )
+            } else {
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&touch_at)
+                    .map(|_| ())
+            };
+
+            if let Err(reason) = touched {
+                return Err(ShellError::labeled_error(
+                    reason.to_string(),
+                    reason.to_string(),
+                    file.span(),
+                ));
             }
         }
 
@@ -980,3 +1085,59 @@ impl Shell for FilesystemShell {
         self.hinter.hint(line, pos, ctx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::copy_then_remove;
+    use std::fs;
+
+    #[test]
+    fn moves_a_file_by_copying_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("notes.txt");
+        let dst = dir.path().join("moved.txt");
+        fs::write(&src, "nu").unwrap();
+
+        copy_then_remove(&src, &dst).unwrap();
+
+        assert!(!src.exists());
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "nu");
+    }
+
+    #[test]
+    fn moves_a_directory_tree_by_copying_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("project");
+        let dst = dir.path().join("moved");
+        fs::create_dir_all(src.join("src/commands")).unwrap();
+        fs::write(src.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(src.join("src/commands/mv.rs"), "fn main() {}").unwrap();
+
+        copy_then_remove(&src, &dst).unwrap();
+
+        assert!(!src.exists());
+        assert_eq!(
+            fs::read_to_string(dst.join("Cargo.toml")).unwrap(),
+            "[package]"
+        );
+        assert_eq!(
+            fs::read_to_string(dst.join("src/commands/mv.rs")).unwrap(),
+            "fn main() {}"
+        );
+    }
+
+    #[test]
+    fn leaves_the_original_when_the_copy_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("notes.txt");
+        let dst = dir.path().join("missing/moved.txt");
+        fs::write(&src, "nu").unwrap();
+
+        let error = copy_then_remove(&src, &dst).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("(the original was left in place)"));
+        assert_eq!(fs::read_to_string(&src).unwrap(), "nu");
+    }
+}
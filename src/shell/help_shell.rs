@@ -3,6 +3,7 @@ use crate::commands::cp::CopyArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
+use crate::commands::touch::TouchArgs;
 use crate::context::SourceMap;
 use crate::object::{TaggedDictBuilder, command_dict};
 use crate::prelude::*;
@@ -173,6 +174,15 @@ impl Shell for HelpShell {
         Ok(OutputStream::empty())
     }
 
+    fn touch(
+        &self,
+        _args: TouchArgs,
+        _name: Span,
+        _path: &str,
+    ) -> Result<OutputStream, ShellError> {
+        Ok(OutputStream::empty())
+    }
+
     fn complete(
         &self,
         line: &str,
@@ -3,6 +3,7 @@ use crate::commands::cp::CopyArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
+use crate::commands::touch::TouchArgs;
 use crate::context::SourceMap;
 use crate::errors::ShellError;
 use crate::prelude::*;
@@ -19,6 +20,7 @@ pub trait Shell: std::fmt::Debug {
     fn mkdir(&self, args: MkdirArgs, name: Span, path: &str) -> Result<OutputStream, ShellError>;
     fn mv(&self, args: MoveArgs, name: Span, path: &str) -> Result<OutputStream, ShellError>;
     fn rm(&self, args: RemoveArgs, name: Span, path: &str) -> Result<OutputStream, ShellError>;
+    fn touch(&self, args: TouchArgs, name: Span, path: &str) -> Result<OutputStream, ShellError>;
     fn path(&self) -> String;
     fn set_path(&mut self, path: String);
 
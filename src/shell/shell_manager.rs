@@ -3,6 +3,7 @@ use crate::commands::cp::CopyArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
+use crate::commands::touch::TouchArgs;
 use crate::errors::ShellError;
 use crate::prelude::*;
 use crate::shell::filesystem_shell::FilesystemShell;
@@ -181,6 +182,26 @@ impl ShellManager {
         }
     }
 
+    pub fn touch(
+        &self,
+        args: TouchArgs,
+        context: &RunnablePerItemContext,
+    ) -> Result<OutputStream, ShellError> {
+        let env = self.shells.lock();
+
+        match env {
+            Ok(x) => {
+                let path = x[self.current_shell].path();
+                x[self.current_shell].touch(args, context.name, &path)
+            }
+            Err(e) => Err(ShellError::labeled_error(
+                format!("Internal error: could not lock {}", e),
+                "Internal error: could not lock",
+                context.name,
+            )),
+        }
+    }
+
     pub fn mv(
         &self,
         args: MoveArgs,
@@ -3,6 +3,7 @@ use crate::commands::cp::CopyArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
+use crate::commands::touch::TouchArgs;
 use crate::context::SourceMap;
 use crate::prelude::*;
 use crate::shell::shell::Shell;
@@ -140,6 +141,14 @@ impl Shell for ValueShell {
         ))
     }
 
+    fn touch(&self, _args: TouchArgs, name: Span, _path: &str) -> Result<OutputStream, ShellError> {
+        Err(ShellError::labeled_error(
+            "touch not currently supported on values",
+            "not currently supported",
+            name,
+        ))
+    }
+
     fn path(&self) -> String {
         self.path.clone()
     }
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use futures::sink::SinkExt;
 
 pub struct InputStream {
     pub(crate) values: BoxStream<'static, Tagged<Value>>,
@@ -82,6 +83,30 @@ impl OutputStream {
 
         values.collect()
     }
+
+    /// By default a stream is lazy: each value is produced only when the next
+    /// command asks for it. A buffered stream is instead produced on a thread of
+    /// its own, running ahead of a slow consumer by up to `capacity` values and
+    /// then waiting for it to catch up.
+    pub fn buffered(self, capacity: usize) -> OutputStream {
+        let (mut sender, receiver) = futures::channel::mpsc::channel(capacity);
+        let mut values = self.values;
+
+        std::thread::spawn(move || {
+            futures::executor::block_on(async move {
+                while let Some(value) = values.next().await {
+                    // The consumer went away, so there is no one left to produce for
+                    if sender.send(value).await.is_err() {
+                        break;
+                    }
+                }
+            })
+        });
+
+        OutputStream {
+            values: receiver.boxed(),
+        }
+    }
 }
 
 impl Stream for OutputStream {
@@ -158,3 +183,28 @@ impl From<Vec<Tagged<Value>>> for OutputStream {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::OutputStream;
+    use crate::object::Value;
+    use crate::prelude::*;
+
+    #[test]
+    fn buffered_streams_yield_the_same_values_in_order() {
+        let values: Vec<Tagged<Value>> = (0..100).map(|n| Value::int(n).tagged_unknown()).collect();
+
+        let stream = OutputStream::from(values).buffered(4);
+        let returned: Vec<ReturnValue> = futures::executor::block_on(stream.values.collect());
+
+        let numbers: Vec<Value> = returned
+            .into_iter()
+            .map(|returned| match returned {
+                Ok(ReturnSuccess::Value(value)) => value.item,
+                other => panic!("expected a value, got {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(numbers, (0..100).map(Value::int).collect::<Vec<_>>());
+    }
+}
@@ -193,7 +193,8 @@ impl FileStructure {
                 let entry = entry?;
                 let path = entry.path();
 
-                if path.is_dir() {
+                // Symlinked directories are kept as links, not walked into
+                if entry.file_type()?.is_dir() {
                     self.build(&path, lvl + 1)?;
                 }
 
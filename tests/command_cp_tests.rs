@@ -167,3 +167,36 @@ fn copies_using_a_glob() {
         ));
     });
 }
+
+#[cfg(unix)]
+#[test]
+fn copies_symlinks_as_links_with_recursive_flag() {
+    Playground::setup("cp_test_8", |dirs, sandbox| {
+        sandbox
+            .within("originals")
+            .with_files(vec![EmptyFile("manifest.txt")])
+            .mkdir("expected");
+
+        std::os::unix::fs::symlink(
+            "manifest.txt",
+            dirs.test().join("originals").join("latest.txt"),
+        )
+        .expect("can not create symlink");
+
+        nu!(
+            cwd: dirs.test(),
+            "cp originals expected --recursive"
+        );
+
+        let copied_link = dirs.test().join("expected/originals/latest.txt");
+
+        assert!(std::fs::symlink_metadata(&copied_link)
+            .expect("link was not copied")
+            .file_type()
+            .is_symlink());
+        assert_eq!(
+            std::fs::read_link(&copied_link).expect("can not read link"),
+            Path::new("manifest.txt")
+        );
+    })
+}
@@ -165,3 +165,42 @@ fn rm_errors_if_attempting_to_delete_two_dot_as_argument() {
         assert!(actual.contains("may not be removed"));
     })
 }
+
+#[test]
+fn rm_removes_every_path_given() {
+    Playground::setup("rm_test_9", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            EmptyFile("yehuda.txt"),
+            EmptyFile("jonathan.txt"),
+            EmptyFile("andres.txt"),
+        ]);
+
+        nu!(
+            cwd: dirs.test(),
+            "rm yehuda.txt jonathan.txt"
+        );
+
+        assert!(!dirs.test().join("yehuda.txt").exists());
+        assert!(!dirs.test().join("jonathan.txt").exists());
+        assert!(dirs.test().join("andres.txt").exists());
+    })
+}
+
+#[test]
+fn rm_stops_at_the_first_path_that_can_not_be_removed() {
+    Playground::setup("rm_test_10", |dirs, sandbox| {
+        sandbox
+            .with_files(vec![EmptyFile("yehuda.txt"), EmptyFile("andres.txt")])
+            .within("src")
+            .with_files(vec![EmptyFile("main.rs")]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "rm yehuda.txt src andres.txt"
+        );
+
+        assert!(actual.contains("is a directory"));
+        assert!(!dirs.test().join("yehuda.txt").exists());
+        assert!(dirs.test().join("andres.txt").exists());
+    })
+}
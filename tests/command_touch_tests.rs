@@ -0,0 +1,64 @@
+mod helpers;
+
+use helpers as h;
+use helpers::{Playground, Stub::*};
+
+use std::path::Path;
+
+#[test]
+fn creates_files() {
+    Playground::setup("touch_test_1", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "touch a.txt b.txt"
+        );
+
+        assert!(h::files_exist_at(
+            vec![Path::new("a.txt"), Path::new("b.txt")],
+            dirs.test()
+        ));
+    })
+}
+
+#[test]
+fn keeps_contents_of_existing_files() {
+    Playground::setup("touch_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("notes.txt", "remember the milk")]);
+
+        nu!(
+            cwd: dirs.test(),
+            "touch notes.txt"
+        );
+
+        let actual = h::file_contents(&dirs.test().join("notes.txt"));
+
+        assert_eq!(actual, "remember the milk");
+    })
+}
+
+#[test]
+fn updates_read_only_files() {
+    Playground::setup("touch_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("locked.txt")]);
+
+        let locked = dirs.test().join("locked.txt");
+        let long_ago = filetime::FileTime::from_unix_time(0, 0);
+        filetime::set_file_mtime(&locked, long_ago).unwrap();
+
+        let mut permissions = std::fs::metadata(&locked).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&locked, permissions).unwrap();
+
+        nu!(
+            cwd: dirs.test(),
+            "touch locked.txt"
+        );
+
+        let metadata = std::fs::metadata(&locked).unwrap();
+        let mut permissions = metadata.permissions();
+        permissions.set_readonly(false);
+        std::fs::set_permissions(&locked, permissions).unwrap();
+
+        assert!(filetime::FileTime::from_last_modification_time(&metadata) > long_ago);
+    })
+}
@@ -66,3 +66,369 @@ fn save_can_write_out_csv() {
         assert!(actual.contains("[list list],A shell for the GitHub era,2018,ISC,nu,0.2.0"));
     })
 }
+
+#[test]
+fn save_can_write_out_a_rendered_table() {
+    Playground::setup("save_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            EmptyFile("yehuda.txt"),
+            EmptyFile("jonathan.txt"),
+            EmptyFile("andres.txt"),
+        ]);
+
+        let expected_file = dirs.test().join("listing.txt");
+
+        nu!(
+            cwd: dirs.test(),
+            "ls *.txt | table --width 80 | save listing.txt"
+        );
+
+        let actual = h::file_contents(expected_file);
+        assert!(actual.contains("name"));
+        assert!(actual.contains("andres.txt"));
+        assert!(actual.contains("yehuda.txt"));
+        assert!(actual.lines().all(|line| line.chars().count() <= 80));
+    })
+}
+
+#[test]
+fn save_can_write_out_bson() {
+    Playground::setup("save_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "rows.json",
+            r#"[{"name": "nu"}, {"name": "rust"}]"#,
+        )]);
+
+        nu!(cwd: dirs.test(), "open rows.json | save rows.bson");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open rows.bson | get name | echo $it"
+        );
+
+        assert_eq!(actual, "nurust");
+    })
+}
+
+#[test]
+fn date_add_shifts_the_current_datetime() {
+    Playground::setup("date_test_1", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "date now | date add 30day | to-json | save --raw later.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("later.json"));
+
+        assert!(actual.ends_with(" UTC\""));
+
+        nu!(
+            cwd: dirs.test(),
+            "date sub 30day | to-json | save --raw earlier.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("earlier.json"));
+
+        assert!(actual.ends_with(" UTC\""));
+    })
+}
+
+#[test]
+fn date_add_shifts_piped_dates() {
+    Playground::setup("date_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent("events.txt", "event,at\nrelease,2019-08-23\n"),
+            FileWithContent(
+                "meeting.json",
+                r#"{"year":2019,"month":8,"day":23,"hour":12,"minute":30,"second":0,"timezone":"+02:00"}"#,
+            ),
+        ]);
+
+        nu!(
+            cwd: dirs.test(),
+            r#"open events.txt | from-csv --schema "event:string,at:date" | get at | date add 7day | to-json | save --raw release.json"#
+        );
+
+        let actual = h::file_contents(dirs.test().join("release.json"));
+
+        assert_eq!(actual, r#""2019-08-30 00:00:00 UTC""#);
+
+        nu!(
+            cwd: dirs.test(),
+            "open meeting.json | date add 1wk | to-json | save --raw meeting_next_week.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("meeting_next_week.json"));
+
+        assert_eq!(actual, r#""2019-08-30 10:30:00 UTC""#);
+    })
+}
+
+#[test]
+fn date_add_reports_dates_out_of_range() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats",
+        "date add 100000000wk"
+    );
+
+    assert!(actual.contains("Date is out of range"));
+
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats",
+        "date add 9223372036854775wk"
+    );
+
+    assert!(actual.contains("Date is out of range"));
+}
+
+#[test]
+fn help_shows_the_usage_and_signature_of_a_command() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "help from-json | get usage | echo $it"
+    );
+
+    assert_eq!(actual, "Parse text as .json and create table.");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "help from-csv | get signature | where name == headerless | get type | echo $it"
+    );
+
+    assert_eq!(actual, "switch");
+}
+
+#[test]
+fn help_lists_every_command_with_its_usage() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "help | where name == from-json | get description | echo $it"
+    );
+
+    assert_eq!(actual, "Parse text as .json and create table.");
+}
+
+#[test]
+fn help_reports_unknown_commands() {
+    let actual = nu_error!(cwd: "tests/fixtures/formats", "help frobnicate");
+
+    assert!(actual.contains("Unknown command 'frobnicate'"));
+}
+
+#[test]
+fn seq_counts_from_start_to_end() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 1 10 | where $it > 7 | echo $it"
+    );
+
+    assert_eq!(actual, "8910");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 0 1 0.5 | to-json | echo $it"
+    );
+
+    assert_eq!(actual, "0.00.51.0");
+}
+
+#[test]
+fn seq_reports_a_step_of_zero() {
+    let actual = nu_error!(cwd: "tests/fixtures/formats", "seq 1 10 0");
+
+    assert!(actual.contains("Can not count by a step of zero"));
+}
+
+#[test]
+fn first_last_and_skip_accept_a_percentage_of_the_rows() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 1 10 | first 50% | echo $it"
+    );
+
+    assert_eq!(actual, "12345");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 1 10 | last 20% | echo $it"
+    );
+
+    assert_eq!(actual, "910");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 1 10 | skip 70% | echo $it"
+    );
+
+    assert_eq!(actual, "8910");
+}
+
+#[test]
+fn random_generates_the_same_values_for_the_same_seed() {
+    let first = nu!(
+        cwd: "tests/fixtures/formats",
+        "random integer 1 100 --seed 42 | echo $it"
+    );
+
+    let second = nu!(
+        cwd: "tests/fixtures/formats",
+        "random integer 1 100 --seed 42 | echo $it"
+    );
+
+    assert_eq!(first, second);
+
+    let actual: i64 = first.parse().unwrap();
+    assert!(actual >= 1 && actual <= 100);
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "random dice 3d1 | echo $it"
+    );
+
+    assert_eq!(actual, "3");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "random uuid | echo $it"
+    );
+
+    assert_eq!(actual.len(), 36);
+}
+
+#[test]
+fn random_reports_unknown_kinds() {
+    let actual = nu_error!(cwd: "tests/fixtures/formats", "random colour");
+
+    assert!(actual.contains("Unknown kind of random value"));
+}
+
+#[test]
+fn random_reports_dice_rolls_that_are_too_large() {
+    let actual = nu_error!(cwd: "tests/fixtures/formats", "random dice 10001d6");
+
+    assert!(actual.contains("Too many dice"));
+
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats",
+        "random dice 1000d9223372036854775807"
+    );
+
+    assert!(actual.contains("Dice roll is too large"));
+}
+
+#[test]
+fn fetch_only_accepts_http_urls() {
+    let actual = nu_error!(cwd: "tests/fixtures/formats", "fetch sample.ini");
+
+    assert!(actual.contains("Expected a url"));
+}
+
+#[test]
+fn post_only_accepts_http_urls() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats",
+        "open sample.ini | to-json | post sample.ini"
+    );
+
+    assert!(actual.contains("Expected a url"));
+}
+
+#[test]
+fn source_runs_each_line_of_a_script() {
+    Playground::setup("source_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "setup.nu",
+            r#"
+                mkdir expected
+                cd expected
+                mkdir nested
+            "#,
+        )]);
+
+        nu!(cwd: dirs.test(), "source setup.nu");
+
+        assert!(dirs.test().join("expected").join("nested").is_dir());
+    })
+}
+
+#[test]
+fn source_stops_at_the_first_failing_line_unless_told_to_keep_going() {
+    Playground::setup("source_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "setup.nu",
+            r#"
+                mkdir first
+                open missing.txt
+                mkdir second
+            "#,
+        )]);
+
+        let actual = nu_error!(cwd: dirs.test(), "source setup.nu");
+
+        assert!(actual.contains("setup.nu:2"));
+        assert!(dirs.test().join("first").is_dir());
+        assert!(!dirs.test().join("second").exists());
+
+        nu_error!(cwd: dirs.test(), "source setup.nu --keep-going");
+
+        assert!(dirs.test().join("second").is_dir());
+    })
+}
+
+#[test]
+fn source_stops_when_a_line_exits() {
+    Playground::setup("source_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "setup.nu",
+            r#"
+                mkdir first
+                exit
+                mkdir second
+            "#,
+        )]);
+
+        nu!(cwd: dirs.test(), "source setup.nu");
+
+        assert!(dirs.test().join("first").is_dir());
+        assert!(!dirs.test().join("second").exists());
+    })
+}
+
+#[test]
+fn median_takes_the_middle_value_or_averages_the_middle_two() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 1 5 | median | echo $it"
+    );
+
+    assert_eq!(actual, "3");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 1 4 | median | echo $it"
+    );
+
+    assert_eq!(actual, "2.5");
+}
+
+#[test]
+fn median_and_mode_read_a_column() {
+    Playground::setup("median_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "scores.json",
+            r#"[{"name": "andres", "score": 7}, {"name": "jonathan", "score": 3}, {"name": "yehuda", "score": 7}]"#,
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), "open scores.json | median score | echo $it");
+
+        assert_eq!(actual, "7");
+
+        let actual = nu!(cwd: dirs.test(), "open scores.json | mode score | echo $it");
+
+        assert_eq!(actual, "7");
+
+        let actual = nu_error!(cwd: dirs.test(), "open scores.json | median name");
+
+        assert!(actual.contains("Can only find the median of numbers"));
+    })
+}
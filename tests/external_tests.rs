@@ -9,3 +9,32 @@ fn external_command() {
 
     assert!(actual.contains("1"));
 }
+
+#[cfg(not(windows))]
+#[test]
+fn external_command_reports_nonzero_exit_codes() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "sh -c \"exit 3\""
+    );
+
+    assert!(actual.contains("External command exited with code 3"));
+}
+
+#[cfg(not(windows))]
+#[test]
+fn external_command_reports_nonzero_exit_codes_of_piped_commands() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "sh -c \"exit 3\" | lines"
+    );
+
+    assert!(actual.contains("External command exited with code 3"));
+
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "sh -c \"exit 3\" | cat"
+    );
+
+    assert!(actual.contains("External command exited with code 3"));
+}
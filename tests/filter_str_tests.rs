@@ -11,7 +11,7 @@ fn can_only_apply_one() {
     );
 
     assert!(
-        actual.contains("Usage: str field [--downcase|--upcase|--to-int|--replace|--find-replace]")
+        actual.contains("Usage: str field [--downcase|--upcase|--to-int|--replace|--find-replace|--substring start,end|--pad-left width|--pad-right width] [--fill char]")
     );
 }
 
@@ -172,3 +172,57 @@ fn find_and_replaces_without_passing_field() {
         assert_eq!(actual, "1-800-5289");
     })
 }
+
+#[test]
+fn substrings() {
+    Playground::setup("plugin_str_test_7", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "data.csv",
+            "code,name\nABC-123,first\nXYZ-987,second\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open data.csv --raw
+                | from-csv
+                | str code --substring "0,3"
+                | get code
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "ABCXYZ");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open data.csv | str code --substring \"-3,100\" | get code | echo $it"
+        );
+
+        assert_eq!(actual, "123987");
+    })
+}
+
+#[test]
+fn pads_strings_to_a_width() {
+    Playground::setup("plugin_str_test_8", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "ids.csv",
+            "id,name\n42,first\n123456789,second\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open ids.csv --raw | from-csv | str id --pad-left 8 --fill 0 | get id | echo $it"
+        );
+
+        assert_eq!(actual, "00000042123456789");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open ids.csv | str name --pad-right 7 --fill \".\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "first..second.");
+    })
+}
@@ -72,252 +72,292 @@ fn converts_structured_table_to_csv_text_skipping_headers_after_conversion() {
 }
 
 #[test]
-fn converts_from_csv_text_to_structured_table() {
-    Playground::setup("filter_from_csv_test_1", |dirs, sandbox| {
+fn converts_structured_table_to_csv_text_quoting_only_when_necessary() {
+    Playground::setup("filter_to_csv_test_3", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "los_tres_caballeros.txt",
+            "csv_text_sample.txt",
             r#"
-                first_name,last_name,rusty_luck
-                Andrés,Robalino,1
-                Jonathan,Turner,1
-                Yehuda,Katz,1
+                name;origin
+                Calcium carbonate;Spain, Madrid
             "#,
         )]);
 
-        let actual = nu!(
+        nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open los_tres_caballeros.txt 
-                | from-csv 
-                | get rusty_luck 
-                | str --to-int 
-                | sum 
-                | echo $it
+                open csv_text_sample.txt
+                | lines
+                | skip 1
+                | split-column ";" name origin
+                | to-csv --quote-style necessary
+                | save --raw quoted.csv
             "#
         ));
 
-        assert_eq!(actual, "3");
+        let actual = h::file_contents(dirs.test().join("quoted.csv"));
+
+        assert!(actual.contains(r#"Calcium carbonate,"Spain, Madrid""#));
     })
 }
 
 #[test]
-fn converts_from_csv_text_skipping_headers_to_structured_table() {
-    Playground::setup("filter_from_csv_test_2", |dirs, sandbox| {
+fn converts_structured_table_to_csv_text_always_quoting() {
+    Playground::setup("filter_to_csv_test_4", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "los_tres_amigos.txt",
+            "csv_text_sample.txt",
             r#"
-                first_name,last_name,rusty_luck
-                Andrés,Robalino,1
-                Jonathan,Turner,1
-                Yehuda,Katz,1
+                name;origin
+                Calcium carbonate;Spain
             "#,
         )]);
 
-        let actual = nu!(
+        nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open los_tres_amigos.txt 
-                | from-csv --headerless 
-                | get Column3 
-                | str --to-int 
-                | sum 
-                | echo $it
+                open csv_text_sample.txt
+                | lines
+                | skip 1
+                | split-column ";" name origin
+                | to-csv --quote-style always
+                | save --raw quoted.csv
             "#
         ));
 
-        assert_eq!(actual, "3");
+        let actual = h::file_contents(dirs.test().join("quoted.csv"));
+
+        assert!(actual.contains(r#""name","origin""#));
+        assert!(actual.contains(r#""Calcium carbonate","Spain""#));
     })
 }
 
 #[test]
-fn can_convert_table_to_json_text_and_from_json_text_back_into_table() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats", h::pipeline(
-        r#"
-            open sgml_description.json 
-            | to-json 
-            | from-json 
-            | get glossary.GlossDiv.GlossList.GlossEntry.GlossSee 
-            | echo $it
-        "#
-    ));
+fn converts_nested_cells_to_json_in_csv_text() {
+    Playground::setup("filter_to_csv_test_6", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "package.json",
+            r#"{"name": "nu", "meta": {"stars": 1, "tags": ["shell"]}}"#,
+        )]);
 
-    assert_eq!(actual, "markup");
+        nu!(
+            cwd: dirs.test(),
+            "open package.json | to-csv --nested json | save --raw package.csv"
+        );
+
+        let actual = h::file_contents(dirs.test().join("package.csv"));
+
+        assert!(actual.contains(r#"nu,"{""stars"":1,""tags"":[""shell""]}""#));
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open package.csv | get meta | from-json | get tags | echo $it"
+        );
+
+        assert_eq!(actual, "shell");
+    })
 }
 
 #[test]
-fn converts_from_json_text_to_structured_table() {
-    Playground::setup("filter_from_json_test_1", |dirs, sandbox| {
-        sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "katz.txt",
-            r#"
-                {
-                    "katz": [
-                        {"name":   "Yehuda", "rusty_luck": 1}, 
-                        {"name": "Jonathan", "rusty_luck": 1}, 
-                        {"name":   "Andres", "rusty_luck": 1},
-                        {"name":"GorbyPuff", "rusty_luck": 1}
-                    ]
-                }
-            "#,
+fn to_csv_and_to_tsv_write_rows_with_different_columns_under_one_header() {
+    Playground::setup("filter_to_csv_test_9", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "rows.json",
+            r#"[
+                {"name": "nu", "size": "1"},
+                {"size": "2", "kind": "shell"},
+                {"kind": "lang", "name": "rust"}
+            ]"#,
+        )]);
+
+        nu!(cwd: dirs.test(), "open rows.json | save rows.csv");
+
+        let actual = h::file_contents(dirs.test().join("rows.csv"));
+
+        assert_eq!(actual, "name,size,kind\nnu,1,\n,2,shell\nrust,,lang\n");
+
+        nu!(cwd: dirs.test(), "open rows.json | save rows.tsv");
+
+        let actual = h::file_contents(dirs.test().join("rows.tsv"));
+
+        assert_eq!(actual, "name\tsize\tkind\nnu\t1\t\n\t2\tshell\nrust\t\tlang\n");
+    })
+}
+
+#[test]
+fn to_csv_and_to_tsv_end_in_a_single_newline() {
+    Playground::setup("filter_to_csv_test_7", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "package.json",
+            r#"{"name": "nu", "kind": "shell"}"#,
+        )]);
+
+        nu!(cwd: dirs.test(), "open package.json | save package.csv");
+        nu!(cwd: dirs.test(), "open package.json | save package.tsv");
+        nu!(
+            cwd: dirs.test(),
+            "open package.json | to-csv --headerless | save --raw headerless.csv"
+        );
+
+        let csv = h::file_contents(dirs.test().join("package.csv"));
+        let tsv = h::file_contents(dirs.test().join("package.tsv"));
+        let headerless = h::file_contents(dirs.test().join("headerless.csv"));
+
+        assert_eq!(csv, "name,kind\nnu,shell\n");
+        assert_eq!(tsv, "name\tkind\nnu\tshell\n");
+        assert_eq!(headerless, "nu,shell\n");
+    })
+}
+
+#[test]
+fn to_csv_writes_dates_as_rfc3339_unless_given_a_format() {
+    Playground::setup("filter_to_csv_test_8", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "release.yml",
+            "name: nu\nreleased: 2024-01-02T03:04:05+02:00\n",
         )]);
 
         let actual = nu!(
             cwd: dirs.test(),
-            "open katz.txt | from-json | get katz | get rusty_luck | sum | echo $it"
+            "open release.yml --raw | from-yaml --parse-dates | to-csv --headerless | echo $it"
         );
 
-        assert_eq!(actual, "4");
+        assert_eq!(actual, "nu,2024-01-02T01:04:05+00:00");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open release.yml --raw | from-yaml --parse-dates | to-csv --headerless --date-format \"%Y-%m-%d\" | echo $it"
+        );
+
+        assert_eq!(actual, "nu,2024-01-02");
     })
 }
 
 #[test]
-fn converts_from_json_text_recognizing_objects_independendtly_to_structured_table() {
-    Playground::setup("filter_from_json_test_2", |dirs, sandbox| {
+fn converts_structured_table_to_csv_text_never_quoting_fails_on_separator() {
+    Playground::setup("filter_to_csv_test_5", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "katz.txt",
+            "csv_text_sample.txt",
             r#"
-                {"name":   "Yehuda", "rusty_luck": 1} 
-                {"name": "Jonathan", "rusty_luck": 1} 
-                {"name":   "Andres", "rusty_luck": 1}
-                {"name":"GorbyPuff", "rusty_luck": 3}
+                name;origin
+                Calcium carbonate;Spain, Madrid
             "#,
         )]);
 
-        let actual = nu!(
+        let actual = nu_error!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open katz.txt 
-                | from-json --objects 
-                | where name == "GorbyPuff" 
-                | get rusty_luck 
-                | echo $it
+                open csv_text_sample.txt
+                | lines
+                | skip 1
+                | split-column ";" name origin
+                | to-csv --quote-style never
             "#
         ));
 
-        assert_eq!(actual, "3");
+        assert!(actual.contains("without quotes"));
     })
 }
 
 #[test]
-fn converts_structured_table_to_json_text() {
-    Playground::setup("filter_to_json_test", |dirs, sandbox| {
+fn converts_from_csv_text_to_structured_table() {
+    Playground::setup("filter_from_csv_test_1", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "sample.txt",
+            "los_tres_caballeros.txt",
             r#"
-                JonAndrehudaTZ,3
-                GorbyPuff,100
+                first_name,last_name,rusty_luck
+                Andrés,Robalino,1
+                Jonathan,Turner,1
+                Yehuda,Katz,1
             "#,
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open sample.txt 
-                | lines 
-                | split-column "," name luck 
-                | pick name 
-                | to-json 
-                | nth 0 
-                | from-json 
-                | get name 
+                open los_tres_caballeros.txt 
+                | from-csv 
+                | get rusty_luck 
+                | str --to-int 
+                | sum 
                 | echo $it
             "#
         ));
 
-        assert_eq!(actual, "JonAndrehudaTZ");
+        assert_eq!(actual, "3");
     })
 }
 
 #[test]
-fn can_convert_table_to_tsv_text_and_from_tsv_text_back_into_table() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "open caco3_plastics.tsv | to-tsv | from-tsv | first 1 | get origin | echo $it"
-    );
-
-    assert_eq!(actual, "SPAIN");
-}
-
-#[test]
-fn converts_structured_table_to_tsv_text() {
-    Playground::setup("filter_to_tsv_test_1", |dirs, sandbox| {
+fn converts_from_csv_text_skipping_headers_to_structured_table() {
+    Playground::setup("filter_from_csv_test_2", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "tsv_text_sample.txt",
+            "los_tres_amigos.txt",
             r#"
-                importer	shipper	tariff_item	name	origin
-                Plasticos Rival	Reverte	2509000000	Calcium carbonate	Spain
-                Tigre Ecuador	OMYA Andina	3824909999	Calcium carbonate	Colombia
+                first_name,last_name,rusty_luck
+                Andrés,Robalino,1
+                Jonathan,Turner,1
+                Yehuda,Katz,1
             "#,
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open tsv_text_sample.txt 
-                | lines 
-                | split-column "\t" a b c d origin  
-                | last 1 
-                | to-tsv
-                | lines 
-                | nth 1 
-                | echo "$it"
+                open los_tres_amigos.txt 
+                | from-csv --headerless 
+                | get Column3 
+                | str --to-int 
+                | sum 
+                | echo $it
             "#
         ));
 
-        assert!(actual.contains("Colombia"));
+        assert_eq!(actual, "3");
     })
 }
 
 #[test]
-fn converts_structured_table_to_tsv_text_skipping_headers_after_conversion() {
-    Playground::setup("filter_to_tsv_test_2", |dirs, sandbox| {
+fn converts_from_csv_text_detecting_comma_separator() {
+    Playground::setup("filter_from_csv_test_3", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "tsv_text_sample.txt",
+            "los_tres_caballeros.txt",
             r#"
-                importer    shipper tariff_item name    origin
-                Plasticos Rival Reverte 2509000000  Calcium carbonate   Spain
-                Tigre Ecuador   OMYA Andina 3824909999  Calcium carbonate   Colombia
+                first_name,last_name,motto
+                Andrés,Robalino,rust | nu
+                Jonathan,Turner,ship it
+                Yehuda,Katz,a; b
             "#,
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open tsv_text_sample.txt 
-                | lines 
-                | split-column "\t" a b c d origin  
-                | last 1 
-                | to-tsv --headerless 
-                | echo "$it"
+                open los_tres_caballeros.txt
+                | from-csv
+                | get last_name
+                | echo $it
             "#
         ));
 
-        assert!(actual.contains("Colombia"));
+        assert_eq!(actual, "RobalinoTurnerKatz");
     })
 }
 
 #[test]
-fn converts_from_tsv_text_to_structured_table() {
-    Playground::setup("filter_from_tsv_test_1", |dirs, sandbox| {
-        sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "los_tres_amigos.txt",
-            r#"
-                first Name	Last Name	rusty_luck
-                Andrés	Robalino	1
-                Jonathan	Turner	1
-                Yehuda	Katz	1
-            "#,
+fn converts_from_csv_text_detecting_tab_separator() {
+    Playground::setup("filter_from_csv_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "los_tres_caballeros.txt",
+            "first_name\tlast_name\trusty_luck\nAndrés\tRobalino\t1\nJonathan\tTurner\t1\nYehuda\tKatz\t1\n",
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open los_tres_amigos.txt 
-                | from-tsv 
-                | get rusty_luck 
-                | str --to-int 
-                | sum 
+                open los_tres_caballeros.txt
+                | from-csv
+                | get rusty_luck
+                | str --to-int
+                | sum
                 | echo $it
             "#
         ));
@@ -327,184 +367,2172 @@ fn converts_from_tsv_text_to_structured_table() {
 }
 
 #[test]
-fn converts_from_tsv_text_skipping_headers_to_structured_table() {
-    Playground::setup("filter_from_tsv_test_2", |dirs, sandbox| {
+fn converts_from_csv_text_ignoring_byte_order_mark() {
+    Playground::setup("filter_from_csv_test_5", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "excel_export.txt",
+            "\u{feff}name,rusty_luck\nAndrés,1\nJonathan,1\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open excel_export.txt
+                | from-csv
+                | get name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "AndrésJonathan");
+    })
+}
+
+#[test]
+fn converts_from_csv_text_skipping_preamble_rows() {
+    Playground::setup("filter_from_csv_test_6", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "los_tres_amigos.txt",
+            "report.txt",
             r#"
-                first Name	Last Name	rusty_luck
-                Andrés	Robalino	1
-                Jonathan	Turner	1
-                Yehuda	Katz	1
+                Exported by Sales Tracker
+                Generated on 2019-09-01
+                first_name,last_name,rusty_luck
+                Andrés,Robalino,1
+                Jonathan,Turner,1
+                Yehuda,Katz,1
             "#,
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open los_tres_amigos.txt
-                | from-tsv --headerless
-                | get Column3
-                | str --to-int
-                | sum
+                open report.txt
+                | from-csv --skip-rows 2
+                | get last_name
                 | echo $it
             "#
         ));
 
-        assert_eq!(actual, "3");
+        assert_eq!(actual, "RobalinoTurnerKatz");
     })
 }
 
 #[test]
-fn can_convert_table_to_bson_and_back_into_table() {
+fn from_csv_stops_after_max_rows() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open sample.bson
-            | to-bson
-            | from-bson
-            | get root
-            | nth 1
-            | get b
+            open caco3_plastics.csv --raw
+            | from-csv --max-rows 3
+            | get origin
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "whel");
-}
+    assert_eq!(actual, "SPAINCOLOMBIASPAIN");
 
-#[test]
-fn can_convert_table_to_sqlite_and_back_into_table() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats", h::pipeline(
-        r#"
-            open sample.db
-            | to-sqlite
-            | from-sqlite
-            | get table_values
-            | nth 2
-            | get x
-            | echo $it
-        "#
-    ));
+    // The number after the rows would be refused, but it is never read
+    Playground::setup("filter_from_csv_test_12", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "chunks.json",
+            r#"["name,lang", "andres,rust", "jonathan,rust", 7]"#,
+        )]);
 
-    assert_eq!(actual, "hello");
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open chunks.json | from-csv --max-rows 1 | get name | echo $it"
+        );
+
+        assert_eq!(actual, "andres");
+    })
 }
 
 #[test]
-fn can_convert_table_to_toml_text_and_from_toml_text_back_into_table() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats", h::pipeline(
-        r#"
-            open cargo_sample.toml
-            | to-toml
-            | from-toml
-            | get package.name
-            | echo $it
-        "#
-    ));
+fn from_csv_can_keep_the_header_row_as_data() {
+    Playground::setup("filter_from_csv_test_7", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("readings.txt", "r1,s1\nr2,s2\n")]);
 
-    assert_eq!(actual, "nu");
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open readings.txt | from-csv --keep-header-row | get r1 | echo $it"
+        );
+
+        assert_eq!(actual, "r1r2");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open readings.txt | from-csv --headerless --keep-header-row | get Column1 | echo $it"
+        );
+
+        assert_eq!(actual, "r1r2");
+    })
 }
 
 #[test]
-fn can_convert_table_to_yaml_text_and_from_yaml_text_back_into_table() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats", h::pipeline(
-        r#"
-            open appveyor.yml 
-            | to-yaml 
-            | from-yaml 
-            | get environment.global.PROJECT_NAME 
-            | echo $it
-        "#
-    ));
+fn from_csv_decodes_bytes_in_the_given_encoding() {
+    Playground::setup("filter_from_csv_test_8", |dirs, _| {
+        // "Andrés" and "José" as Latin-1, which is not valid UTF-8
+        std::fs::write(
+            dirs.test().join("latin1.txt"),
+            b"name\nAndr\xe9s\nJos\xe9\n",
+        )
+        .expect("can not write the Latin-1 fixture");
 
-    assert_eq!(actual, "nushell");
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open latin1.txt | from-csv --encoding latin1 | get name | echo $it"
+        );
+
+        assert_eq!(actual, "AndrésJosé");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open latin1.txt | from-csv --strict"
+        );
+
+        assert!(actual.contains("Could not decode as UTF-8"));
+    })
 }
 
 #[test]
-fn can_sort_by_column() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats", h::pipeline(
-        r#"
-            open cargo_sample.toml --raw 
-            | lines 
-            | skip 1 
-            | first 4 
-            | split-column "=" 
-            | sort-by Column1 
-            | skip 1 
-            | first 1 
-            | get Column1 
-            | trim 
-            | echo $it
-        "#
-    ));
+fn from_csv_skips_comment_lines() {
+    Playground::setup("filter_from_csv_test_9", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "readings.txt",
+            "# exported nightly\nsensor,value\nr1,10\n  # sensor r2 offline\nr3,30\n",
+        )]);
 
-    assert_eq!(actual, "description");
+        let actual = nu!(
+            cwd: dirs.test(),
+            r##"open readings.txt | from-csv --comment "#" | get sensor | echo $it"##
+        );
+
+        assert_eq!(actual, "r1r3");
+    })
 }
 
 #[test]
-fn can_split_by_column() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats", h::pipeline(
-        r#"
-            open cargo_sample.toml --raw 
-            | lines 
-            | skip 1 
-            | first 1 
-            | split-column "=" 
-            | get Column1 
-            | trim 
-            | echo $it
-        "#
-    ));
+fn from_csv_reads_columns_as_the_types_in_a_schema() {
+    Playground::setup("filter_from_csv_test_10", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent(
+                "members.txt",
+                "name,age,joined\nandres,30,2019-08-23\njonathan,40,2019-05-10\n",
+            ),
+            FileWithContent(
+                "broken.txt",
+                "name,age,joined\nandres,30,2019-08-23\nyehuda,unknown,2019-05-10\n",
+            ),
+        ]);
 
-    assert_eq!(actual, "name");
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"open members.txt | from-csv --schema "name:string,age:int,joined:date" | where age > 35 | get name | echo $it"#
+        );
+
+        assert_eq!(actual, "jonathan");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            r#"open broken.txt | from-csv --schema "name:string,age:int,joined:date""#
+        );
+
+        assert!(actual.contains(r#"Could not read "unknown" as int"#));
+        assert!(actual.contains("row 2, column 'age'"));
+    })
 }
 
 #[test]
-fn can_sum() {
+fn from_csv_numbers_rows_by_the_line_they_came_from() {
+    Playground::setup("filter_from_csv_test_11", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "readings.txt",
+            "exported by sensor-hub\nsensor,value\nkitchen,21\n# recalibrated\nattic,27\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r##"open readings.txt | from-csv --skip-rows 1 --comment "#" --with-line-numbers | get _line | echo $it"##
+        );
+
+        assert_eq!(actual, "35");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r##"open readings.txt | from-csv --skip-rows 1 --comment "#" --line-column source_line | where sensor == attic | get source_line | echo $it"##
+        );
+
+        assert_eq!(actual, "5");
+    })
+}
+
+#[test]
+fn converts_from_fixed_width_text_to_structured_table() {
+    Playground::setup("filter_from_fixed_width_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "report.txt",
+            "first     last      rusty_luck\nAndrés    Robalino  1\nJonathan  Turner    1\nYehuda    Katz      1\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open report.txt
+                | from-fixed-width 0 10 20
+                | where first == "Andrés"
+                | get last
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "Robalino");
+    })
+}
+
+#[test]
+fn from_fixed_width_finds_the_columns_from_the_header() {
+    Playground::setup("filter_from_fixed_width_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "report.txt",
+            "first     last      rusty_luck\nAndrés    Robalino  1\nJonathan  Turner    2\nYehuda    Katz      3\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open report.txt
+                | from-fixed-width
+                | where last == "Turner"
+                | get rusty_luck
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "2");
+
+        let actual = nu_error!(cwd: dirs.test(), "open report.txt | from-fixed-width 0 -10");
+
+        assert!(actual.contains("Column positions can't be negative"));
+    })
+}
+
+#[test]
+fn can_convert_table_to_json_text_and_from_json_text_back_into_table() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
             open sgml_description.json 
-            | get glossary.GlossDiv.GlossList.GlossEntry.Sections 
-            | sum 
+            | to-json 
+            | from-json 
+            | get glossary.GlossDiv.GlossList.GlossEntry.GlossSee 
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "203")
+    assert_eq!(actual, "markup");
 }
 
 #[test]
-fn can_filter_by_unit_size_comparison() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "ls | where size > 1kb | sort-by size | get name | first 1 | trim | echo $it"
-    );
+fn converts_from_json_text_to_structured_table() {
+    Playground::setup("filter_from_json_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "katz.txt",
+            r#"
+                {
+                    "katz": [
+                        {"name":   "Yehuda", "rusty_luck": 1}, 
+                        {"name": "Jonathan", "rusty_luck": 1}, 
+                        {"name":   "Andres", "rusty_luck": 1},
+                        {"name":"GorbyPuff", "rusty_luck": 1}
+                    ]
+                }
+            "#,
+        )]);
 
-    assert_eq!(actual, "cargo_sample.toml");
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open katz.txt | from-json | get katz | get rusty_luck | sum | echo $it"
+        );
+
+        assert_eq!(actual, "4");
+    })
 }
 
 #[test]
-fn can_get_last() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "ls | sort-by name | last 1 | get name | trim | echo $it"
-    );
+fn converts_from_json_text_recognizing_objects_independendtly_to_structured_table() {
+    Playground::setup("filter_from_json_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "katz.txt",
+            r#"
+                {"name":   "Yehuda", "rusty_luck": 1} 
+                {"name": "Jonathan", "rusty_luck": 1} 
+                {"name":   "Andres", "rusty_luck": 1}
+                {"name":"GorbyPuff", "rusty_luck": 3}
+            "#,
+        )]);
 
-    assert_eq!(actual, "utf16.ini");
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open katz.txt 
+                | from-json --objects 
+                | where name == "GorbyPuff" 
+                | get rusty_luck 
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "3");
+    })
 }
 
 #[test]
-fn can_get_reverse_first() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "ls | sort-by name | reverse | first 1 | get name | trim | echo $it"
-    );
+fn converts_only_listed_columns_to_json() {
+    Playground::setup("filter_to_json_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sample.json",
+            r#"{"c": 1, "a": 2, "b": 3}"#,
+        )]);
 
-    assert_eq!(actual, "utf16.ini");
+        nu!(
+            cwd: dirs.test(),
+            r#"open sample.json | to-json --columns "b,c,missing" | save --raw subset.json"#
+        );
+
+        let actual = h::file_contents(dirs.test().join("subset.json"));
+
+        assert_eq!(actual, r#"{"b":3,"c":1}"#);
+    })
+}
+
+#[test]
+fn from_json_reports_why_it_could_not_parse() {
+    Playground::setup("filter_from_json_test_6", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "broken.json",
+            "{\n  \"name\": \"nu\",\n  \"tags\": [1, 2}\n}",
+        )]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open broken.json --raw | from-json"
+        );
+
+        assert!(actual.contains("Could not parse as JSON"));
+        assert!(actual.contains("at line 3"));
+    })
+}
+
+#[test]
+fn from_json_can_select_a_subtree_with_a_pointer() {
+    Playground::setup("filter_from_json_test_7", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "big.json",
+            r#"{"data": {"items": [{"name": "nu"}, {"name": "rust"}], "a/b": "slash"}}"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"open big.json --raw | from-json --pointer "/data/items" | get name | echo $it"#
+        );
+
+        assert_eq!(actual, "nurust");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"open big.json --raw | from-json --pointer "/data/a~1b" | echo $it"#
+        );
+
+        assert_eq!(actual, "slash");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            r#"open big.json --raw | from-json --pointer "/data/missing""#
+        );
+
+        assert!(actual.contains("JSON pointer not found"));
+    })
+}
+
+#[test]
+fn converts_to_json5_with_unquoted_identifier_keys() {
+    Playground::setup("filter_to_json_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "config.json",
+            r#"{"name": "nu", "max-width": 80, "plugins": [{"_id": 1}]}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open config.json | to-json --json5 | save --raw config.json5"
+        );
+
+        let actual = h::file_contents(dirs.test().join("config.json5"));
+
+        assert_eq!(actual, r#"{name:"nu","max-width":80,plugins:[{_id:1}]}"#);
+    })
+}
+
+#[test]
+fn converts_json_integers_wider_than_i64_without_loss() {
+    Playground::setup("filter_from_json_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "ids.json",
+            r#"{"id":123456789012345678901234567890,"min":-123456789012345678901234567890,"distance":1e19}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open ids.json --raw | from-json | to-json | save --raw roundtrip.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("roundtrip.json"));
+
+        assert_eq!(actual, r#"{"id":123456789012345678901234567890,"min":-123456789012345678901234567890,"distance":1e19}"#);
+    })
+}
+
+#[test]
+fn converts_json_text_back_and_forth_preserving_key_order() {
+    Playground::setup("filter_from_json_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sample.json",
+            r#"{"c": 1, "a": 2, "b": 3}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open sample.json --raw | from-json | to-json | save --raw roundtrip.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("roundtrip.json"));
+
+        assert_eq!(actual, r#"{"c":1,"a":2,"b":3}"#);
+    })
+}
+
+#[test]
+fn converts_structured_table_to_json_text() {
+    Playground::setup("filter_to_json_test", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "sample.txt",
+            r#"
+                JonAndrehudaTZ,3
+                GorbyPuff,100
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open sample.txt 
+                | lines 
+                | split-column "," name luck 
+                | pick name 
+                | to-json 
+                | nth 0 
+                | from-json 
+                | get name 
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "JonAndrehudaTZ");
+    })
+}
+
+#[test]
+fn can_convert_table_to_tsv_text_and_from_tsv_text_back_into_table() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open caco3_plastics.tsv | to-tsv | from-tsv | first 1 | get origin | echo $it"
+    );
+
+    assert_eq!(actual, "SPAIN");
+}
+
+#[test]
+fn converts_structured_table_to_tsv_text() {
+    Playground::setup("filter_to_tsv_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "tsv_text_sample.txt",
+            r#"
+                importer	shipper	tariff_item	name	origin
+                Plasticos Rival	Reverte	2509000000	Calcium carbonate	Spain
+                Tigre Ecuador	OMYA Andina	3824909999	Calcium carbonate	Colombia
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open tsv_text_sample.txt 
+                | lines 
+                | split-column "\t" a b c d origin  
+                | last 1 
+                | to-tsv
+                | lines 
+                | nth 1 
+                | echo "$it"
+            "#
+        ));
+
+        assert!(actual.contains("Colombia"));
+    })
+}
+
+#[test]
+fn converts_structured_table_to_tsv_text_skipping_headers_after_conversion() {
+    Playground::setup("filter_to_tsv_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "tsv_text_sample.txt",
+            r#"
+                importer    shipper tariff_item name    origin
+                Plasticos Rival Reverte 2509000000  Calcium carbonate   Spain
+                Tigre Ecuador   OMYA Andina 3824909999  Calcium carbonate   Colombia
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open tsv_text_sample.txt 
+                | lines 
+                | split-column "\t" a b c d origin  
+                | last 1 
+                | to-tsv --headerless 
+                | echo "$it"
+            "#
+        ));
+
+        assert!(actual.contains("Colombia"));
+    })
+}
+
+#[test]
+fn converts_from_tsv_text_to_structured_table() {
+    Playground::setup("filter_from_tsv_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_amigos.txt",
+            r#"
+                first Name	Last Name	rusty_luck
+                Andrés	Robalino	1
+                Jonathan	Turner	1
+                Yehuda	Katz	1
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt 
+                | from-tsv 
+                | get rusty_luck 
+                | str --to-int 
+                | sum 
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "3");
+    })
+}
+
+#[test]
+fn converts_from_tsv_text_skipping_headers_to_structured_table() {
+    Playground::setup("filter_from_tsv_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_amigos.txt",
+            r#"
+                first Name	Last Name	rusty_luck
+                Andrés	Robalino	1
+                Jonathan	Turner	1
+                Yehuda	Katz	1
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt
+                | from-tsv --headerless
+                | get Column3
+                | str --to-int
+                | sum
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "3");
+    })
+}
+
+#[test]
+fn can_convert_table_to_bson_and_back_into_table() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sample.bson
+            | to-bson
+            | from-bson
+            | get root
+            | nth 1
+            | get b
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "whel");
+}
+
+#[test]
+fn can_convert_decimals_to_bson_decimal128_and_back() {
+    Playground::setup("filter_to_bson_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "invoice.json",
+            r#"{"amount": 1234.5678}"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open invoice.json
+                | to-bson --decimal128
+                | from-bson
+                | get amount
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1234.5678");
+    })
+}
+
+#[test]
+fn to_bson_keeps_the_key_order_of_the_row() {
+    Playground::setup("filter_to_bson_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "fruits.json",
+            r#"{"zebra": 1, "apple": 2, "mango": {"kiwi": 3, "banana": 4}}"#,
+        )]);
+
+        let round_trip = || {
+            nu!(
+                cwd: dirs.test(),
+                "open fruits.json | to-bson | from-bson | to-json | save --raw round_trip.json"
+            );
+
+            h::file_contents(dirs.test().join("round_trip.json"))
+        };
+
+        let first = round_trip();
+
+        assert_eq!(
+            first,
+            r#"[{"zebra":1,"apple":2,"mango":{"kiwi":3,"banana":4}}]"#
+        );
+        assert_eq!(first, round_trip());
+    })
+}
+
+#[test]
+fn to_bson_reads_extended_json_dates() {
+    Playground::setup("filter_to_bson_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "export.json",
+            r#"{"created": {"$date": "2024-01-02T03:04:05Z"}, "updated": {"$date": {"$numberLong": "1704164645000"}}}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open export.json | to-bson | from-bson | to-json | save --raw round_trip.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("round_trip.json"));
+
+        assert_eq!(
+            actual,
+            r#"[{"created":"2024-01-02 03:04:05 UTC","updated":"2024-01-02 03:04:05 UTC"}]"#
+        );
+    })
+}
+
+#[test]
+fn to_bson_stores_dates_with_an_offset_as_the_same_utc_instant() {
+    Playground::setup("filter_to_bson_test_6", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "export.json",
+            r#"{"created": {"$date": "2024-01-02T03:04:05+02:00"}}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open export.json | to-bson | from-bson | to-json | save --raw round_trip.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("round_trip.json"));
+
+        assert_eq!(actual, r#"[{"created":"2024-01-02 01:04:05 UTC"}]"#);
+    })
+}
+
+#[test]
+fn to_bson_validate_reports_rows_instead_of_writing_them() {
+    Playground::setup("filter_to_bson_test_7", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "export.json",
+            r#"[{"name": "nu"}, {"name": "bson"}]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open export.json | to-bson --validate | get valid | echo $it"
+        );
+
+        assert_eq!(actual, "truetrue");
+    })
+}
+
+#[test]
+fn to_bson_keeps_integers_wider_than_i64_as_number_long() {
+    Playground::setup("filter_to_bson_test_5", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("ids.json", r#"{"name": "nu"}"#)]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open ids.json
+                | calc id "1234567890123456789012345"
+                | to-bson
+                | from-bson
+                | get id
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1234567890123456789012345");
+    })
+}
+
+#[test]
+fn from_bson_can_keep_extended_json_for_round_trips() {
+    Playground::setup("filter_from_bson_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "export.json",
+            r#"{"_id": {"$object_id": "5d5a2e0a6b3b5a0c9c1f4e21"}, "created": {"$date": "2024-01-02T03:04:05Z"}}"#,
+        )]);
+
+        let expected =
+            r#"[{"_id":{"$object_id":"5d5a2e0a6b3b5a0c9c1f4e21"},"created":{"$date":"2024-01-02T03:04:05.000Z"}}]"#;
+
+        nu!(
+            cwd: dirs.test(),
+            "open export.json | to-bson | from-bson --extended-json | to-json | save --raw once.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("once.json"));
+
+        assert_eq!(actual, expected);
+
+        nu!(
+            cwd: dirs.test(),
+            "open export.json | to-bson | from-bson --extended-json | to-bson | from-bson --extended-json | to-json | save --raw twice.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("twice.json"));
+
+        assert_eq!(actual, expected);
+    })
+}
+
+#[test]
+fn can_convert_table_to_sqlite_and_back_into_table() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sample.db
+            | to-sqlite
+            | from-sqlite
+            | get table_values
+            | nth 2
+            | get x
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "hello");
+}
+
+#[test]
+fn can_convert_table_to_toml_text_and_from_toml_text_back_into_table() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | to-toml
+            | from-toml
+            | get package.name
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "nu");
+}
+
+#[test]
+fn from_json_keeps_integral_floats_apart_from_integers() {
+    Playground::setup("filter_from_json_test_5", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "numbers.json",
+            r#"{"values": [1, 1.0]}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open numbers.json | to-json | save --raw round_trip.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("round_trip.json"));
+
+        assert_eq!(actual, r#"{"values":[1,1.0]}"#);
+    })
+}
+
+#[test]
+fn can_convert_table_to_yaml_text_and_from_yaml_text_back_into_table() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open appveyor.yml 
+            | to-yaml 
+            | from-yaml 
+            | get environment.global.PROJECT_NAME 
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "nushell");
+}
+
+#[test]
+fn to_yaml_writes_flow_or_block_style() {
+    Playground::setup("filter_to_yaml_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "point.json",
+            r#"{"name": "a, b", "coords": [1, 2]}"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open point.json | to-yaml --flow | save --raw flow.yml"
+        );
+
+        let flow = h::file_contents(dirs.test().join("flow.yml"));
+
+        assert_eq!(flow, r#"{name: "a, b", coords: [1, 2]}"#);
+
+        nu!(
+            cwd: dirs.test(),
+            "open point.json | to-yaml | save --raw block.yml"
+        );
+
+        let block = h::file_contents(dirs.test().join("block.yml"));
+
+        assert!(block.contains("coords:\n  - 1\n  - 2"));
+        assert!(!block.contains("{"));
+    })
+}
+
+#[test]
+fn select_keeps_columns_in_the_given_order() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | select origin importer
+            | first 1
+            | to-csv
+            | lines
+            | first 1
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "origin,importer");
+}
+
+#[test]
+fn can_build_a_string_from_pipeline_and_arguments() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | first 1
+            | get origin
+            | build-string "/" 2016
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "SPAIN/2016");
+}
+
+#[test]
+fn can_group_rows_into_overlapping_windows() {
+    Playground::setup("filter_window_test_1", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "open {}/caco3_plastics.csv | pick origin | first 4 | window 2 --stride 1 | to-json | save --raw windows.txt",
+            dirs.formats()
+        );
+
+        let actual = h::file_contents(dirs.test().join("windows.txt"));
+        let windows: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0], r#"[{"origin":"SPAIN"},{"origin":"COLOMBIA"}]"#);
+        assert_eq!(windows[2], r#"[{"origin":"SPAIN"},{"origin":"TURKEY"}]"#);
+    })
+}
+
+#[test]
+fn converts_yaml_merging_anchored_mappings() {
+    Playground::setup("filter_from_yaml_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "ci.yml",
+            "defaults: &defaults\n  image: rust\n  retries: 2\nbuild:\n  <<: *defaults\n  script: cargo build\ntest:\n  <<: *defaults\n  retries: 5\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open ci.yml --raw
+                | from-yaml --merge-keys
+                | get build.image
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "rust");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open ci.yml --raw
+                | from-yaml --merge-keys
+                | get test.retries
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "5");
+    })
+}
+
+#[test]
+fn converts_yaml_binary_nodes_to_binary() {
+    Playground::setup("filter_from_yaml_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "greeting.yml",
+            "name: greeting\ndata: !!binary SGVsbG8=\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open greeting.yml --raw | from-yaml | get data | to-json | save --raw data.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("data.json"));
+
+        assert_eq!(actual, "[72.0,101.0,108.0,108.0,111.0]");
+    })
+}
+
+#[test]
+fn from_yaml_parse_dates_reads_timestamps_as_dates() {
+    Playground::setup("filter_from_yaml_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "release.yml",
+            "name: nu\nreleased: 2024-01-02T03:04:05+02:00\nplanned: 2024-02-01\nlabel: \"2024-03-01\"\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open release.yml --raw | from-yaml --parse-dates | to-json | save --raw release.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("release.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"name":"nu","released":"2024-01-02 01:04:05 UTC","planned":"2024-02-01 00:00:00 UTC","label":"2024-03-01"}"#
+        );
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open release.yml --raw | from-yaml | get released | echo $it"
+        );
+
+        assert_eq!(actual, "2024-01-02T03:04:05+02:00");
+    })
+}
+
+#[test]
+fn from_xml_simple_collapses_text_elements() {
+    Playground::setup("filter_from_xml_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "book.xml",
+            r#"<book id="1"><title>Nu</title><author>Yehuda</author><author>Jonathan</author></book>"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open book.xml --raw | from-xml | to-json | save --raw nested.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("nested.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"book":[{"title":["Nu"]},{"author":["Yehuda"]},{"author":["Jonathan"]}]}"#
+        );
+
+        nu!(
+            cwd: dirs.test(),
+            "open book.xml --raw | from-xml --simple | to-json | save --raw simple.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("simple.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"book":{"title":"Nu","author":["Yehuda","Jonathan"]}}"#
+        );
+
+        let actual = nu!(
+            cwd: "tests/fixtures/formats",
+            "open jonathan.xml --raw | from-xml --simple | get rss.channel.title | echo $it"
+        );
+
+        assert_eq!(actual, "Jonathan Turner");
+    })
+}
+
+#[test]
+fn can_sort_by_column() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml --raw 
+            | lines 
+            | skip 1 
+            | first 4 
+            | split-column "=" 
+            | sort-by Column1 
+            | skip 1 
+            | first 1 
+            | get Column1 
+            | trim 
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "description");
+}
+
+#[test]
+fn can_split_by_column() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml --raw 
+            | lines 
+            | skip 1 
+            | first 1 
+            | split-column "=" 
+            | get Column1 
+            | trim 
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "name");
+}
+
+#[test]
+fn can_sum() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sgml_description.json 
+            | get glossary.GlossDiv.GlossList.GlossEntry.Sections 
+            | sum 
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "203")
+}
+
+#[test]
+fn can_filter_by_unit_size_comparison() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "ls | where size > 1kb | sort-by size | get name | first 1 | trim | echo $it"
+    );
+
+    assert_eq!(actual, "cargo_sample.toml");
+}
+
+#[test]
+fn can_get_last() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "ls | sort-by name | last 1 | get name | trim | echo $it"
+    );
+
+    assert_eq!(actual, "utf16.ini");
+}
+
+#[test]
+fn can_get_reverse_first() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "ls | sort-by name | reverse | first 1 | get name | trim | echo $it"
+    );
+
+    assert_eq!(actual, "utf16.ini");
+}
+
+#[test]
+fn can_count_words_chars_and_bytes_with_size() {
+    Playground::setup("filter_size_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("essay.txt", "naïve café\tcrème\n")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open essay.txt
+                | size
+                | get words chars bytes
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "31720");
+    })
+}
+
+#[test]
+fn can_calculate_a_column_from_other_columns() {
+    Playground::setup("filter_calc_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "orders.json",
+            r#"[{"price": 3, "qty": 4}, {"price": 1.5, "qty": 3}]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open orders.json
+                | calc total "(price + 1) * qty"
+                | get total
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "167.5");
+    })
+}
+
+#[test]
+fn calc_reports_division_by_zero() {
+    Playground::setup("filter_calc_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "orders.json",
+            r#"[{"price": 3, "qty": 0}]"#,
+        )]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open orders.json | calc unit \"price / qty\""
+        );
+
+        assert!(actual.contains("division by zero"));
+    })
+}
+
+#[test]
+fn can_output_the_values_of_a_row() {
+    Playground::setup("filter_values_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "config.json",
+            r#"{"name": "nu", "shell": "yes", "editor": "vim"}"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open config.json | values | echo $it"
+        );
+
+        assert_eq!(actual, "nuyesvim");
+    })
+}
+
+#[test]
+fn par_each_runs_a_block_on_each_row_keeping_order() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | first 4
+            | par-each --threads 2 { $it.origin }
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "SPAINCOLOMBIASPAINTURKEY");
+}
+
+#[test]
+fn par_each_unordered_runs_a_block_on_every_row() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | first 4
+            | par-each --unordered --threads 3 { $it }
+            | sort-by origin
+            | get origin
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "COLOMBIASPAINSPAINTURKEY");
+}
+
+#[test]
+fn collect_outputs_the_whole_input_as_one_list() {
+    Playground::setup("filter_collect_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "origins.csv",
+            "origin,year\nSPAIN,2019\nCOLOMBIA,2018\nSPAIN,2017\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open origins.csv | pick origin | collect | to-json | save --raw collected.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("collected.json"));
+
+        assert_eq!(
+            actual,
+            r#"[{"origin":"SPAIN"},{"origin":"COLOMBIA"},{"origin":"SPAIN"}]"#
+        );
+    })
+}
+
+#[test]
+fn inspect_passes_values_through_unchanged() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | inspect
+            | first 1
+            | get origin
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "SPAIN");
+}
+
+#[test]
+fn from_yaml_reports_the_line_it_could_not_parse() {
+    Playground::setup("filter_from_yaml_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent("config.yml", "name: nu\n- version\n"),
+            FileWithContent("release.yml", "name: nu\nversion: 0.4: beta\n"),
+        ]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open config.yml --raw | from-yaml"
+        );
+
+        assert!(actual.contains("Could not parse as YAML at line 2, column 1"));
+        assert!(actual.contains("- version"));
+
+        // Columns count from 1, so the second colon is the 13th character
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open release.yml --raw | from-yaml"
+        );
+
+        assert!(actual.contains("Could not parse as YAML at line 2, column 13"));
+    })
+}
+
+#[test]
+fn from_toml_reports_the_line_it_could_not_parse() {
+    Playground::setup("filter_from_toml_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "config.toml",
+            "[package]\nname = \"nu\"\nversion \"0.2.0\"\n",
+        )]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open config.toml --raw | from-toml"
+        );
+
+        assert!(actual.contains("line 3"));
+        assert!(actual.contains("version \"0.2.0\""));
+    })
+}
+
+#[test]
+fn from_toml_keeps_arrays_of_tables_in_file_order() {
+    Playground::setup("filter_from_toml_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "servers.toml",
+            r#"
+                [[servers]]
+                name = "beta"
+
+                [servers.config]
+                port = 8081
+
+                [[servers]]
+                name = "alpha"
+
+                [servers.config]
+                port = 8080
+            "#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open servers.toml | get servers | to-json | save --raw servers.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("servers.json"));
+
+        assert_eq!(
+            actual,
+            "{\"config\":{\"port\":8081},\"name\":\"beta\"}\n{\"config\":{\"port\":8080},\"name\":\"alpha\"}"
+        );
+    })
+}
+
+#[test]
+fn can_filter_with_a_quoted_predicate() {
+    Playground::setup("filter_predicate_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "files.json",
+            r#"[
+                {"name": "main.rs", "size": 2048},
+                {"name": "lib.rs", "size": 10},
+                {"name": "notes.md", "size": 4096}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open files.json
+                | where "size > 1kb and name =~ '\.rs$'"
+                | get name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "main.rs");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open files.json
+                | where "not (name =~ 'rs$') or size < 100"
+                | get name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "lib.rsnotes.md");
+    })
+}
+
+#[test]
+fn where_has_keeps_rows_with_the_column_present() {
+    Playground::setup("filter_where_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "events.json",
+            r#"[
+                {"id": 1, "error": "timeout"},
+                {"id": 2},
+                {"id": 3, "error": null}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open events.json | where --has error | get id | echo $it"
+        );
+
+        assert_eq!(actual, "13");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open events.json | where --has error \"id > 1\" | get id | echo $it"
+        );
+
+        assert_eq!(actual, "3");
+    })
+}
+
+#[test]
+fn where_contains_looks_inside_text_and_lists() {
+    Playground::setup("filter_where_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "tasks.json",
+            r#"[
+                {"name": "write tests", "tags": ["Urgent", "code"]},
+                {"name": "Test release", "tags": ["release"]},
+                {"name": "lunch", "tags": ["urgent"]}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where \"name contains test\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "write tests");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where --insensitive \"name contains test\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "write testsTest release");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where name contains \"test\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "write tests");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where --insensitive name contains test | get name | echo $it"
+        );
+
+        assert_eq!(actual, "write testsTest release");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where \"tags contains urgent\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "lunch");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where --insensitive \"tags contains urgent\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "write testslunch");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open tasks.json | where --insensitive \"name == 'test release'\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "Test release");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open tasks.json | where --insensitive { $it.name == 'lunch' }"
+        );
+
+        assert!(actual.contains("Blocks can't ignore case"));
+    })
+}
+
+#[test]
+fn where_is_checks_the_type_of_a_column() {
+    Playground::setup("filter_where_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "readings.json",
+            r#"[
+                {"name": "first", "value": 10},
+                {"name": "second", "value": 2.5},
+                {"name": "third", "value": "n/a"}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open readings.json | where \"value is int\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "first");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open readings.json | where value is int | get name | echo $it"
+        );
+
+        assert_eq!(actual, "first");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open readings.json | where \"value is string or value is decimal\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "secondthird");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open readings.json | where \"value is number\""
+        );
+
+        assert!(actual.contains("Could not parse condition"));
+    })
+}
+
+#[test]
+fn where_leaves_out_rows_with_empty_cells_when_comparing() {
+    Playground::setup("filter_where_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "people.json",
+            r#"[
+                {"name": "andres", "age": 30},
+                {"name": "jonathan", "age": null},
+                {"name": "yehuda", "age": 12}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open people.json | where age > 18 | get name | echo $it"
+        );
+
+        assert_eq!(actual, "andres");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open people.json | where age != null | get name | echo $it"
+        );
+
+        assert_eq!(actual, "andresyehuda");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open people.json | where \"age < 18 or age == null\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "jonathanyehuda");
+    })
+}
+
+#[test]
+fn where_treats_missing_columns_as_empty_cells() {
+    Playground::setup("filter_where_test_5", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "people.json",
+            r#"[
+                {"name": "andres", "age": 30},
+                {"name": "jonathan"},
+                {"name": "yehuda", "age": 12}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open people.json | where age > 18 | get name | echo $it"
+        );
+
+        assert_eq!(actual, "andres");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open people.json | where age == null | get name | echo $it"
+        );
+
+        assert_eq!(actual, "jonathan");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open people.json | where \"age < 18 or age == null\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "jonathanyehuda");
+    })
+}
+
+#[test]
+fn can_take_rows_while_a_predicate_matches() {
+    Playground::setup("filter_predicate_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "files.json",
+            r#"[
+                {"name": "main.rs", "size": 2048},
+                {"name": "lib.rs", "size": 10},
+                {"name": "notes.md", "size": 4096}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open files.json | take-while \"size > 1kb\" | get name | echo $it"
+        );
+
+        assert_eq!(actual, "main.rs");
+    })
+}
+
+#[test]
+fn take_while_reports_rows_it_could_not_test() {
+    Playground::setup("filter_predicate_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "files.json",
+            r#"[{"name": "main.rs", "size": "big"}]"#,
+        )]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open files.json | take-while size < 4 | get name | echo $it"
+        );
+
+        assert!(actual.contains("Coercion error"));
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open files.json | skip-while size < 4 | get name | echo $it"
+        );
+
+        assert!(actual.contains("Coercion error"));
+    })
+}
+
+#[test]
+fn reports_predicates_it_could_not_parse() {
+    Playground::setup("filter_predicate_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "files.json",
+            r#"[{"name": "main.rs", "size": 2048}]"#,
+        )]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open files.json | where \"size > and name == main.rs\""
+        );
+
+        assert!(actual.contains("Could not parse condition"));
+    })
+}
+
+#[test]
+fn rotates_a_table_in_either_direction() {
+    Playground::setup("filter_rotate_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("matrix.txt", "1,2\n3,4\n")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open matrix.txt | from-csv --headerless --keep-header-row | rotate | get Column1 | echo $it"
+        );
+
+        assert_eq!(actual, "34");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open matrix.txt | from-csv --headerless --keep-header-row | rotate --counter-clockwise | get Column1 | echo $it"
+        );
+
+        assert_eq!(actual, "21");
+    })
+}
+
+#[test]
+fn zip_pairs_rows_with_a_list_up_to_the_shorter() {
+    Playground::setup("filter_zip_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "origins.csv",
+            "origin,year\nSPAIN,2019\nCOLOMBIA,2018\nSPAIN,2017\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open origins.csv | get origin | zip [a b] | to-json | save --raw zipped.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("zipped.json"));
+
+        assert_eq!(actual, "[\"SPAIN\",\"a\"]\n[\"COLOMBIA\",\"b\"]");
+    })
+}
+
+#[test]
+fn get_reads_bytes_out_of_binary_data() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.db --raw | get 0 | echo $it"
+    );
+
+    assert_eq!(actual, "83");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.db --raw | get \"0..16\" | get \"4..8\" | get 0 | echo $it"
+    );
+
+    assert_eq!(actual, "116");
+
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats",
+        "open sample.db --raw | get \"0..16\" | get 16"
+    );
+
+    assert!(actual.contains("Index out of range"));
+}
+
+#[test]
+fn join_combines_rows_sharing_a_key() {
+    Playground::setup("filter_join_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent("a.csv", "id,name\n1,andres\n2,jonathan\n3,yehuda\n"),
+            FileWithContent("b.csv", "id,lang\n1,rust\n3,ruby\n3,rust\n"),
+        ]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open a.csv | join id b.csv | to-json | save --raw inner.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("inner.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"id":"1","name":"andres","lang":"rust"}
+{"id":"3","name":"yehuda","lang":"ruby"}
+{"id":"3","name":"yehuda","lang":"rust"}"#
+        );
+
+        nu!(
+            cwd: dirs.test(),
+            "open a.csv | join id b.csv --left | nth 1 | to-json | save --raw left.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("left.json"));
+
+        assert_eq!(actual, r#"{"id":"2","name":"jonathan","lang":null}"#);
+    })
+}
+
+#[test]
+fn split_by_groups_rows_between_boundaries() {
+    Playground::setup("filter_split_by_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "log.txt",
+            "start\nok\n---\n---\nretry\n---\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            r#"open log.txt | lines | split-by { $it == "---" } | to-json | save --raw block.json"#
+        );
+
+        let actual = h::file_contents(dirs.test().join("block.json"));
+
+        assert_eq!(actual, "[\"start\",\"ok\"]\n[\"retry\"]");
+
+        nu!(
+            cwd: dirs.test(),
+            r#"open log.txt | lines | split-by "---" | to-json | save --raw value.json"#
+        );
+
+        let actual = h::file_contents(dirs.test().join("value.json"));
+
+        assert_eq!(actual, "[\"start\",\"ok\"]\n[\"retry\"]");
+    })
+}
+
+#[test]
+fn from_logfmt_reads_a_row_for_each_line() {
+    Playground::setup("filter_from_logfmt_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "app.log",
+            r#"level=info msg="hello world" count=3
+level=warn msg="say \"hi\"" took=0.5
+starting up
+"#,
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open app.log | lines | from-logfmt | to-json | save --raw rows.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("rows.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"level":"info","msg":"hello world","count":"3"}
+{"level":"warn","msg":"say \"hi\"","took":"0.5"}
+{"msg":"starting up"}"#
+        );
+
+        nu!(
+            cwd: dirs.test(),
+            "open app.log | lines | first 1 | from-logfmt --coerce | to-json | save --raw coerced.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("coerced.json"));
+
+        assert_eq!(actual, r#"{"level":"info","msg":"hello world","count":3}"#);
+    })
+}
+
+#[test]
+fn from_clf_reads_a_row_for_each_request() {
+    Playground::setup("filter_from_clf_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "access.log",
+            r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08"
+10.0.0.2 - - [10/Oct/2000:13:56:01 -0700] "GET /missing HTTP/1.0" 404 -
+not a log line
+"#,
+        )]);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "open access.log | lines | from-clf"
+        );
+
+        assert!(actual.contains("Could not parse as a common log line"));
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open access.log | lines | from-clf --skip-errors | where status == 404 | get request | echo $it"
+        );
+
+        assert_eq!(actual, "GET /missing HTTP/1.0");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open access.log | lines | from-clf --skip-errors | first 1 | get user_agent | echo $it"
+        );
+
+        assert_eq!(actual, "Mozilla/4.08");
+    })
+}
+
+#[test]
+fn duplicates_shows_rows_that_appear_more_than_once() {
+    Playground::setup("filter_duplicates_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "emails.csv",
+            "name,address\nann,a@nu.sh\nbob,b@nu.sh\ncat,a@nu.sh\ndan,d@nu.sh\neve,a@nu.sh\nfay,d@nu.sh\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open emails.csv | get address | duplicates | echo $it"
+        );
+
+        assert_eq!(actual, "a@nu.shd@nu.sh");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open emails.csv | get address | duplicates --count | get count | echo $it"
+        );
+
+        assert_eq!(actual, "32");
+    })
+}
+
+#[test]
+fn uniq_drops_repeated_rows_everywhere_or_only_when_adjacent() {
+    Playground::setup("filter_uniq_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("numbers.txt", "1\n1\n2\n1\n")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open numbers.txt | lines | uniq | echo $it"
+        );
+
+        assert_eq!(actual, "12");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open numbers.txt | lines | uniq --adjacent | echo $it"
+        );
+
+        assert_eq!(actual, "121");
+    })
+}
+
+#[test]
+fn group_by_groups_rows_by_a_column_or_a_block() {
+    Playground::setup("filter_group_by_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "files.json",
+            r#"[
+                {"name": "a", "type": "file", "size": 5},
+                {"name": "b", "type": "dir", "size": 50},
+                {"name": "c", "type": "file", "size": 500}
+            ]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open files.json | group-by type | get file | get name | echo $it"
+        );
+
+        assert_eq!(actual, "ac");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open files.json | group-by { $it.size > 10 } | get true | get name | echo $it"
+        );
+
+        assert_eq!(actual, "bc");
+    })
+}
+
+#[test]
+fn tokenize_splits_text_into_words() {
+    Playground::setup("filter_tokenize_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("essay.txt", "The cat,\nthe hat.")]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open essay.txt | tokenize | to-json | save --raw words.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("words.json"));
+
+        assert_eq!(actual, "\"The\"\n\"cat,\"\n\"the\"\n\"hat.\"");
+
+        nu!(
+            cwd: dirs.test(),
+            "open essay.txt | tokenize --punctuation | to-json | save --raw bare_words.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("bare_words.json"));
+
+        assert_eq!(actual, "\"The\"\n\"cat\"\n\"the\"\n\"hat\"");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"open essay.txt | tokenize --pattern "[a-z]at" | echo $it"#
+        );
+
+        assert_eq!(actual, "cathat");
+    })
+}
+
+#[test]
+fn round_rounds_a_decimal_column() {
+    Playground::setup("filter_round_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sales.json",
+            r#"[{"amount": 2.125}, {"amount": 3.456}, {"amount": 7}]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sales.json | round amount 2 | get amount | echo $it"
+        );
+
+        assert_eq!(actual, "2.133.467");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sales.json | round amount 2 --mode half-even | get amount | echo $it"
+        );
+
+        assert_eq!(actual, "2.123.467");
+    })
+}
+
+#[test]
+fn round_reports_cells_that_are_not_numbers() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats",
+        "open caco3_plastics.csv | round origin 2"
+    );
+
+    assert!(actual.contains("Can only round numbers"));
+}
+
+#[test]
+fn get_extracts_the_column_from_every_row() {
+    Playground::setup("filter_get_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sales.json",
+            r#"[{"item": "pen", "price": 1}, {"item": "ink", "price": 20}, {"item": "pad", "price": 300}]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sales.json | get price | echo $it"
+        );
+
+        assert_eq!(actual, "120300");
+    })
+}
+
+#[test]
+fn converts_vcf_cards_to_rows() {
+    Playground::setup("filter_from_vcf_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "contacts.vcf",
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Ada Lovelace\r\nTEL;TYPE=CELL:+44 20 7946 0000\r\nNOTE:Wrote the first\r\n  program\r\nEND:VCARD\r\nBEGIN:VCARD\r\nFN:Alan Turing\r\nEMAIL:alan@example.com\r\nEMAIL:turing@example.com\r\nEND:VCARD\r\nBEGIN:VCARD\r\nFN:Broken\r\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open contacts.vcf | first 2 | get fn | echo $it"
+        );
+
+        assert_eq!(actual, "Ada LovelaceAlan Turing");
+
+        nu!(
+            cwd: dirs.test(),
+            "open contacts.vcf | first 1 | pick tel note | to-json | save --raw tel.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("tel.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"tel":{"value":"+44 20 7946 0000","params":{"type":"CELL"}},"note":"Wrote the first program"}"#
+        );
+
+        nu!(
+            cwd: dirs.test(),
+            "open contacts.vcf | nth 2 | to-json | save --raw broken.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("broken.json"));
+
+        assert_eq!(actual, r#"{"fn":"Broken","malformed":true}"#);
+    })
+}
+
+#[test]
+fn converts_ics_events_to_rows() {
+    Playground::setup("filter_from_ics_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "schedule.ics",
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nSUMMARY:Standup\\, daily\r\nDTSTART:20240102T030405Z\r\nDTEND:20240102T033405Z\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\nEND:VALARM\r\nEND:VEVENT\r\nBEGIN:VTODO\r\nSUMMARY:Write the\r\n  report\r\nEND:VTODO\r\nBEGIN:VEVENT\r\nSUMMARY:Retro\r\nDTSTART:20240105T100000Z\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open schedule.ics | first 1 | to-json | save --raw standup.json"
+        );
+
+        let actual = h::file_contents(dirs.test().join("standup.json"));
+
+        assert_eq!(
+            actual,
+            r#"{"summary":"Standup, daily","dtstart":"2024-01-02 03:04:05 UTC","dtend":"2024-01-02 03:34:05 UTC"}"#
+        );
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open schedule.ics --raw | from-ics --all | get component | echo $it"
+        );
+
+        assert_eq!(actual, "valarmveventvtodovevent");
+    })
+}
+
+#[test]
+fn to_json_array_chunks_join_into_one_array() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | first 3
+            | to-json --array
+            | from-json
+            | get origin
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "SPAINCOLOMBIASPAIN");
+
+    Playground::setup("filter_to_json_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "origins.csv",
+            "origin,year\nSPAIN,2019\nCOLOMBIA,2018\n",
+        )]);
+
+        nu!(
+            cwd: dirs.test(),
+            "open origins.csv | get origin | to-json --array | save --raw origins.json"
+        );
+
+        // save --raw writes each chunk on its own line
+        let actual = h::file_contents(dirs.test().join("origins.json"));
+
+        assert_eq!(actual, "[\n\"SPAIN\"\n,\"COLOMBIA\"\n]");
+    })
+}
+
+#[test]
+fn to_json_as_object_merges_key_value_rows() {
+    Playground::setup("filter_to_json_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent(
+                "settings.json",
+                r#"[
+                    {"name": "host", "value": "nu.sh"},
+                    {"name": "port", "value": 8080},
+                    {"name": "tls", "value": true}
+                ]"#,
+            ),
+            FileWithContent(
+                "repeated.json",
+                r#"[
+                    {"name": "host", "value": "nu.sh"},
+                    {"name": "host", "value": "example.com"}
+                ]"#,
+            ),
+        ]);
+
+        nu!(
+            cwd: dirs.test(),
+            r#"open settings.json | to-json --as-object "name,value" | save --raw settings_object.json"#
+        );
+
+        let actual = h::file_contents(dirs.test().join("settings_object.json"));
+
+        assert_eq!(actual, r#"{"host":"nu.sh","port":8080,"tls":true}"#);
+
+        nu!(
+            cwd: dirs.test(),
+            r#"open repeated.json | to-json --as-object "name,value" | save --raw repeated_object.json"#
+        );
+
+        let actual = h::file_contents(dirs.test().join("repeated_object.json"));
+
+        assert_eq!(actual, r#"{"host":"example.com"}"#);
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            r#"open repeated.json | to-json --as-object "name,value" --strict"#
+        );
+
+        assert!(actual.contains("Key 'host' appears more than once"));
+    })
 }